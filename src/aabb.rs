@@ -0,0 +1,53 @@
+// Using base tools of mersh.
+use mersh::base::*;
+
+/// Data structure for defining axis-aligned bounding boxes.
+#[derive(Clone, Default, Debug)]
+pub struct Aabb {
+    /// Corner of the box with minimal coordinates.
+    pub min: Pnt3d,
+    /// Corner of the box with maximal coordinates.
+    pub max: Pnt3d,
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of axis-aligned bounding box services.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl Aabb {
+    /// Creating new axis-aligned bounding box from its extremal corners.
+    ///
+    /// * `min` - corner of the box with minimal coordinates.
+    /// * `max` - corner of the box with maximal coordinates.
+    ///
+    pub fn new(min: Pnt3d, max: Pnt3d) -> Self
+    {
+        Aabb{ min, max }
+    }
+
+    /// Testing if two boxes intersect. Boxes that are only touching are not intersecting.
+    ///
+    /// * `other` - box to test intersection with.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::aabb::*;
+    /// use mersh::base::*;
+    ///
+    /// let a = Aabb::new(Pnt3d::new(0.0, 0.0, 0.0), Pnt3d::new(1.0, 1.0, 1.0));
+    /// let b = Aabb::new(Pnt3d::new(0.5, 0.5, 0.5), Pnt3d::new(1.5, 1.5, 1.5));
+    /// let c = Aabb::new(Pnt3d::new(1.0, 0.0, 0.0), Pnt3d::new(2.0, 1.0, 1.0));
+    ///
+    /// assert!(a.intersects(&b));
+    /// assert!(!a.intersects(&c));
+    /// ```
+    pub fn intersects(&self, other: &Aabb) -> bool
+    {
+        self.min.coords.x < other.max.coords.x && other.min.coords.x < self.max.coords.x
+            && self.min.coords.y < other.max.coords.y && other.min.coords.y < self.max.coords.y
+            && self.min.coords.z < other.max.coords.z && other.min.coords.z < self.max.coords.z
+    }
+}
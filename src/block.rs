@@ -1,25 +1,130 @@
 // Using base tools of mersh.
 use mersh::base::*;
 
-/// Data structure for defining blocks.
-#[derive(Clone, Default, Debug)]
+// Using quaternions for block orientation.
+use quaternion::*;
+
+// Using axis-aligned bounding boxes.
+use aabb::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Data structure for defining blocks. With the `serde` feature, blocks can be serialized and
+/// deserialized, points and vectors being represented as arrays of coordinates.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # fn main() {
+/// extern crate serde_json;
+/// use rody::block::*;
+///
+/// let block = BlockBuilder::new()
+///     .set_mass_density(1.2)
+///     .set_lengths(1., 0.5, 0.25)
+///     .set_initial_position(1.0, 2.0, 3.0)
+///     .set_initial_velocity(-1.0, 0.0, 0.0)
+///     .get();
+///
+/// let json = serde_json::to_string(&block).unwrap();
+/// let loaded: Block = serde_json::from_str(&json).unwrap();
+///
+/// assert!(block.approx_eq(&loaded, 1e-12));
+/// # }
+/// # #[cfg(not(feature = "serde"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Block {
     /// Total mass of the block.
     pub mass: f64,
     /// Associated length in each direction.
     pub lengths: [f64; 3],
     /// Associated position of the block center of mass.
+    #[cfg_attr(feature = "serde", serde(with = "::serde_adapters::pnt3d"))]
     pub position: Pnt3d,
     /// Associated velocity of the block center of mass.
+    #[cfg_attr(feature = "serde", serde(with = "::serde_adapters::vec3d"))]
     pub velocity: Vec3d,
-    // .... To Do : Angles and angular velocity.
+    /// Associated orientation of the block around its center of mass, as a unit quaternion.
+    pub orientation: Quaternion,
+    /// Associated angular velocity of the block around its center of mass.
+    #[cfg_attr(feature = "serde", serde(with = "::serde_adapters::vec3d"))]
+    pub angular_velocity: Vec3d,
+    /// Force accumulated on the block, applied and cleared by integrators at each time step.
+    #[cfg_attr(feature = "serde", serde(with = "::serde_adapters::vec3d"))]
+    pub force: Vec3d,
+    /// Torque accumulated on the block around its center of mass, applied and cleared by
+    /// integrators at each time step.
+    #[cfg_attr(feature = "serde", serde(with = "::serde_adapters::vec3d"))]
+    pub torque: Vec3d,
+    /// Restitution coefficient of the block, between 0 (perfectly plastic) and 1 (perfectly elastic).
+    pub restitution: f64,
+    /// Friction coefficient of the block.
+    pub friction: f64,
+    /// Flag for static blocks, which never move and behave as if they had an infinite mass.
+    pub is_static: bool,
+    /// Flag for sleeping blocks, which are skipped by integrators and collision responses until
+    /// they are woken up.
+    pub asleep: bool,
 }
 
+/// Faces of a block, defined by the indexes of their corners and ordered counter-clockwise when
+/// seen from outside of the block.
+pub(crate) const BLOCK_FACES: [[usize; 4]; 6] = [
+    [0, 4, 6, 2], [1, 3, 7, 5],
+    [0, 1, 5, 4], [2, 6, 7, 3],
+    [0, 2, 3, 1], [4, 5, 7, 6],
+];
+
 /// Helper class for building blocks properly.
 #[derive(Clone, Default, Debug)]
 pub struct BlockBuilder {
     /// Block under construction.
     block: Block,
+    /// Flag indicating that the stored mass is the total mass of the block, not its mass density.
+    absolute_mass: bool,
+}
+
+/// Errors raised when building invalid blocks.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BuildError {
+    /// A length of the block is not strictly positive, the index of the direction and the length
+    /// are given.
+    NonPositiveLength(usize, f64),
+    /// The mass density or the total mass of the block is negative.
+    NegativeMass(f64),
+    /// An input of the builder is not a number, the name of the input is given.
+    NotANumber(&'static str),
+    /// The restitution coefficient of the block is not within [0, 1].
+    InvalidRestitution(f64),
+    /// The friction coefficient of the block is negative.
+    NegativeFriction(f64),
+}
+
+/// Errors raised when formatting blocks.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormatError {
+    /// A token of the data string is not recognized, the token is given.
+    UnknownToken(String),
+}
+
+/// Errors raised when parsing blocks from strings.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseBlockError {
+    /// An entry of the string is not of the form `key=value`, the entry is given.
+    MalformedEntry(String),
+    /// A key of the string is not recognized, the key is given.
+    UnknownKey(String),
+    /// A value cannot be parsed as a number, the key and the value are given.
+    InvalidNumber(String, String),
+    /// A value does not have the expected number of components, the key and the number of
+    /// components found are given.
+    InvalidComponentCount(String, usize),
+    /// The parsed block is not valid.
+    InvalidBlock(BuildError),
 }
 
 /// Helper class for formatting blocks.
@@ -27,10 +132,28 @@ pub struct BlockBuilder {
 pub struct BlockFormatter<'a> {
     /// Reference to block to format.
     block: &'a Block,
-    /// Index of data to be formmated.
-    data_index: Vec<u8>,
+    /// Index of data to be formmated, with optional number of decimal overriding the default one.
+    data_index: Vec<(u8, Option<usize>)>,
     /// Number of decimal for formatting values.
     decimal: usize,
+    /// Separator between formatted values, values are padded with single spaces if not set.
+    separator: Option<String>,
+    /// Minimum width of formatted values.
+    width: usize,
+    /// Flag for formatting values using scientific notation.
+    scientific: bool,
+}
+
+/// Helper class for formatting a single value of a block.
+struct FormattedValue {
+    /// Value to format.
+    value: f64,
+    /// Number of decimal for formatting the value.
+    decimal: usize,
+    /// Minimum width of the formatted value.
+    width: usize,
+    /// Flag for formatting the value using scientific notation.
+    scientific: bool,
 }
 
 //////////////////////////////////////////////////////////////
@@ -52,22 +175,116 @@ impl BlockBuilder {
     /// assert!(block.get_volume().abs() < 1e-12);
     /// assert!(block.position.coords.norm() < 1e-12);
     /// assert!(block.velocity.coords.norm() < 1e-12);
+    /// assert!((block.orientation.w - 1.0).abs() < 1e-12);
+    /// assert!(block.angular_velocity.coords.norm() < 1e-12);
+    /// assert!((block.restitution - 1.0).abs() < 1e-12);
+    /// assert!(block.friction.abs() < 1e-12);
     /// ```
     pub fn new() -> Self
     {
         BlockBuilder::default()
     }
 
+    /// Resetting builder, internal component are restored to default values.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass(2.0)
+    ///     .set_lengths(1., 0.5, 0.25)
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .reset()
+    ///     .set_mass_density(1.0)
+    ///     .get();
+    ///
+    /// assert!(block.mass.abs() < 1e-12);
+    /// assert!(block.get_volume().abs() < 1e-12);
+    /// assert!(block.velocity.coords.norm() < 1e-12);
+    /// ```
+    pub fn reset(&mut self) -> &mut Self
+    {
+        *self = BlockBuilder::default();
+        self
+    }
+
+    /// Creating new builder initialized from an existing block. The mass of the block is stored as
+    /// its total mass, so that it is not multiplied again by the volume when calling `get()`.
+    ///
+    /// * `block` - block used for initializing the builder.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(1.2)
+    ///     .set_lengths(1., 0.5, 0.25)
+    ///     .set_initial_position(1.0, 2.0, 3.0)
+    ///     .get();
+    ///
+    /// let edited = BlockBuilder::from_block(&block)
+    ///     .set_initial_velocity(0.0, 0.0, -1.0)
+    ///     .get();
+    ///
+    /// assert!((edited.mass - block.mass).abs() < 1e-12);
+    /// assert!((edited.get_volume() - block.get_volume()).abs() < 1e-12);
+    /// assert!((edited.position.coords.z - 3.0).abs() < 1e-12);
+    /// assert!((edited.velocity.coords.z + 1.0).abs() < 1e-12);
+    /// ```
+    pub fn from_block(block: &Block) -> Self
+    {
+        BlockBuilder{ block: block.clone(), absolute_mass: true }
+    }
+
     /// Setting mass density of the block. The input paramater corresponds the the "per elementary
     /// volume" mass, the total mass of the block is the mass density times the volume of the block.
     ///
     /// * `mass_density` - the the "per elementary volume" mass, the total mass of the block is the
-    /// mass density times the volume of the block.
+    ///   mass density times the volume of the block.
+    ///
+    /// This setter and `set_mass` are mutually exclusive, the last call wins.
     ///
     pub fn set_mass_density(&mut self, mass_density: f64) -> &mut Self
     {
         // Storing mass density, total mass computed when calling get() method.
         self.block.mass = mass_density;
+        self.absolute_mass = false;
+        self
+    }
+
+    /// Setting total mass of the block. Contrary to `set_mass_density`, the input parameter is not
+    /// multiplied by the volume of the block when calling `get()`.
+    ///
+    /// * `mass` - total mass of the block.
+    ///
+    /// This setter and `set_mass_density` are mutually exclusive, the last call wins.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(3.0)
+    ///     .set_mass(2.0)
+    ///     .set_lengths(1., 0.5, 0.25)
+    ///     .get();
+    ///
+    /// assert!((block.mass - 2.0).abs() < 1e-12);
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass(2.0)
+    ///     .set_mass_density(3.0)
+    ///     .set_lengths(1., 0.5, 0.25)
+    ///     .get();
+    ///
+    /// assert!((block.mass - 3.0 * 0.125).abs() < 1e-12);
+    /// ```
+    pub fn set_mass(&mut self, mass: f64) -> &mut Self
+    {
+        self.block.mass = mass;
+        self.absolute_mass = true;
         self
     }
 
@@ -107,6 +324,113 @@ impl BlockBuilder {
         self
     }
 
+    /// Setting initial orientation of the block from Euler angles, using the Z-Y-X intrinsic
+    /// convention: the block is rotated by `yaw` around the z axis, then by `pitch` around its new
+    /// y axis, then by `roll` around its new x axis.
+    ///
+    /// * `roll` - angle of the rotation around the x axis, in radians.
+    /// * `pitch` - angle of the rotation around the y axis, in radians.
+    /// * `yaw` - angle of the rotation around the z axis, in radians.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(2., 1., 1.)
+    ///     .set_orientation_euler(0.0, 0.0, 0.5 * std::f64::consts::PI)
+    ///     .get();
+    ///
+    /// // Corner (+, +, +) of the block frame, rotated by a quarter turn around z.
+    /// let corner = &block.get_corners()[7];
+    ///
+    /// assert!((corner.coords.x + 0.5).abs() < 1e-12);
+    /// assert!((corner.coords.y - 1.0).abs() < 1e-12);
+    /// assert!((corner.coords.z - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn set_orientation_euler(&mut self, roll: f64, pitch: f64, yaw: f64) -> &mut Self
+    {
+        self.block.orientation = Quaternion::from_euler(roll, pitch, yaw);
+        self
+    }
+
+    /// Setting initial angular velocity of the block.
+    ///
+    /// * `wx` - First coordinate of the initial angular velocity of the block.
+    /// * `wy` - Second coordinate of the initial angular velocity of the block.
+    /// * `wz` - Third coordinate of the initial angular velocity of the block.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .set_angular_velocity(0.0, 0.0, 2.0)
+    ///     .get();
+    ///
+    /// assert!((block.angular_velocity.coords.z - 2.0).abs() < 1e-12);
+    /// assert!((block.velocity.coords.x - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn set_angular_velocity(&mut self, wx: f64, wy: f64, wz: f64) -> &mut Self
+    {
+        self.block.angular_velocity = Vec3d::new(wx, wy, wz);
+        self
+    }
+
+    /// Setting restitution coefficient of the block.
+    ///
+    /// * `restitution` - restitution coefficient, between 0 (perfectly plastic) and 1 (perfectly
+    ///   elastic).
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_restitution(0.8)
+    ///     .get();
+    ///
+    /// assert!((block.restitution - 0.8).abs() < 1e-12);
+    /// ```
+    pub fn set_restitution(&mut self, restitution: f64) -> &mut Self
+    {
+        self.block.restitution = restitution;
+        self
+    }
+
+    /// Setting friction coefficient of the block.
+    ///
+    /// * `friction` - friction coefficient.
+    ///
+    pub fn set_friction(&mut self, friction: f64) -> &mut Self
+    {
+        self.block.friction = friction;
+        self
+    }
+
+    /// Setting block as static, i.e. immovable. Static blocks are skipped by integrators and behave
+    /// as if they had an infinite mass in collisions.
+    ///
+    /// * `is_static` - true for static blocks.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_static(true)
+    ///     .get();
+    ///
+    /// assert!(block.is_static);
+    /// assert!(block.get_inverse_mass().abs() < 1e-12);
+    /// ```
+    pub fn set_static(&mut self, is_static: bool) -> &mut Self
+    {
+        self.block.is_static = is_static;
+        self
+    }
+
     /// Accessing built block.
     ///
     /// # Examples
@@ -127,14 +451,67 @@ impl BlockBuilder {
     /// ```
     pub fn get(&mut self) -> Block
     {
-        // Computing block mass from mass density.
-        self.block.mass *= self.block.get_volume();
+        // Computing block mass from mass density, unless total mass has been set.
+        if !self.absolute_mass { self.block.mass *= self.block.get_volume(); }
 
         // Returning built block.
         let built_block = self.block.clone();
-        self.block = Block::default();
+        self.reset();
         built_block
     }
+
+    /// Accessing built block, after checking that its properties are valid. On error, the builder
+    /// is left untouched so that invalid properties can be fixed.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(1.2)
+    ///     .set_lengths(1., 0.5, 0.25)
+    ///     .try_get();
+    ///
+    /// assert!(block.is_ok());
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(1.2)
+    ///     .set_lengths(1., -0.5, 0.25)
+    ///     .try_get();
+    ///
+    /// assert_eq!(block.unwrap_err(), BuildError::NonPositiveLength(1, -0.5));
+    /// ```
+    pub fn try_get(&mut self) -> Result<Block, BuildError>
+    {
+        self.validate()?;
+        Ok(self.get())
+    }
+
+    /// Checking properties of the block under construction.
+    ///
+    fn validate(&self) -> Result<(), BuildError>
+    {
+        let b = &self.block;
+        if b.mass.is_nan() { return Err(BuildError::NotANumber("mass")); }
+        if b.mass < 0.0 { return Err(BuildError::NegativeMass(b.mass)); }
+
+        for (i, l) in b.lengths.iter().enumerate()
+        {
+            if l.is_nan() { return Err(BuildError::NotANumber("lengths")); }
+            if *l <= 0.0 { return Err(BuildError::NonPositiveLength(i, *l)); }
+        }
+
+        let is_nan = |v: &Vec3d| v.coords.x.is_nan() || v.coords.y.is_nan() || v.coords.z.is_nan();
+        let p = &b.position.coords;
+        if p.x.is_nan() || p.y.is_nan() || p.z.is_nan() { return Err(BuildError::NotANumber("position")); }
+        if is_nan(&b.velocity) { return Err(BuildError::NotANumber("velocity")); }
+        if is_nan(&b.angular_velocity) { return Err(BuildError::NotANumber("angular velocity")); }
+
+        if !(0.0..=1.0).contains(&b.restitution) { return Err(BuildError::InvalidRestitution(b.restitution)); }
+        if b.friction.is_nan() { return Err(BuildError::NotANumber("friction")); }
+        if b.friction < 0.0 { return Err(BuildError::NegativeFriction(b.friction)); }
+        Ok(())
+    }
 }
 
 //////////////////////////////////////////////////////////////
@@ -143,6 +520,28 @@ impl BlockBuilder {
 //////////////////////////////////////////////////////////////
 //////////////////////////////////////////////////////////////
 
+impl Default for Block {
+    /// Default block is perfectly elastic and frictionless, all other properties being zero.
+    ///
+    fn default() -> Self
+    {
+        Block{
+            mass: 0.0,
+            lengths: [0.0; 3],
+            position: Pnt3d::default(),
+            velocity: Vec3d::default(),
+            orientation: Quaternion::default(),
+            angular_velocity: Vec3d::default(),
+            force: Vec3d::default(),
+            torque: Vec3d::default(),
+            restitution: 1.0,
+            friction: 0.0,
+            is_static: false,
+            asleep: false,
+        }
+    }
+}
+
 impl Block {
     /// Computing block volume.
     ///
@@ -161,67 +560,2122 @@ impl Block {
         self.lengths[0] * self.lengths[1] * self.lengths[2]
     }
 
-    /// Creating formatter of current block instance.
+    /// Computing block surface area.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
     ///
-    /// * `data_str` - TO DO !
+    /// assert!((block.get_surface_area() - 6.0).abs() < 1e-12);
+    ///
+    /// let flat = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 0.)
+    ///     .get();
     ///
-    pub fn format(&self, data_str: &str, decimal: usize) -> BlockFormatter
+    /// assert!((flat.get_surface_area() - 2.0).abs() < 1e-12);
+    /// ```
+    pub fn get_surface_area(&self) -> f64
     {
-        BlockFormatter{ block: &self, data_index: BlockFormatter::parse_data_str(data_str), decimal: decimal }
+        let [lx, ly, lz] = self.lengths;
+        2.0 * (lx * ly + ly * lz + lz * lx)
     }
-}
 
-//////////////////////////////////////////////////////////////
-//////////////////////////////////////////////////////////////
-// Implementation of block internal data formatter.
-//////////////////////////////////////////////////////////////
-//////////////////////////////////////////////////////////////
+    /// Computing mass density of the block, i.e. its mass divided by its volume. Blocks with zero
+    /// volume are associated to a zero density.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(1.2)
+    ///     .set_lengths(1., 0.5, 0.25)
+    ///     .get();
+    ///
+    /// assert!((block.get_density() - 1.2).abs() < 1e-12);
+    /// ```
+    pub fn get_density(&self) -> f64
+    {
+        let volume = self.get_volume();
+        if volume != 0.0 { self.mass / volume } else { 0.0 }
+    }
 
-use std::fmt;
+    /// Computing translational kinetic energy of the block.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_velocity(-1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// assert!((block.get_kinetic_energy() - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn get_kinetic_energy(&self) -> f64
+    {
+        let v = self.velocity.coords.norm();
+        0.5 * self.mass * v * v
+    }
 
-impl<'a> BlockFormatter<'a> {
-    /// Parsing input data string to data index.
+    /// Computing rotational kinetic energy of the block, using the inertia tensor in the block
+    /// frame and the angular velocity expressed in the same frame.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass_density(12.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// block.angular_velocity = Vec3d::new(0.0, 0.0, 1.0);
     ///
-    fn parse_data_str(data_str: &str) -> Vec<u8>
+    /// assert!((block.get_rotational_kinetic_energy() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn get_rotational_kinetic_energy(&self) -> f64
     {
-        let mut data_index = Vec::new();
-        let split : Vec<&str> = data_str.split_whitespace().collect();
-        for s in split.iter()
-        {
-            match &*String::from(*s).to_lowercase() {
-                "_" => for i in 0..6 { data_index.push(i); },
-                "p" => for i in 0..3 { data_index.push(i); },
-                "v" => for i in 3..6 { data_index.push(i); },
-                "px" => data_index.push(0),
-                "py" => data_index.push(1),
-                "pz" => data_index.push(2),
-                "vx" => data_index.push(3),
-                "vy" => data_index.push(4),
-                "vz" => data_index.push(5),
-                _ => (),
-            };
-        }
-        data_index
+        let w = self.orientation.inverse_rotate(&self.angular_velocity);
+        let inertia = self.get_inertia_tensor();
+        0.5 * (inertia[0][0] * w.coords.x * w.coords.x
+            + inertia[1][1] * w.coords.y * w.coords.y
+            + inertia[2][2] * w.coords.z * w.coords.z)
     }
-}
 
-impl<'a> fmt::Display for BlockFormatter<'a> {
-    /// Implementation of display trait for a block formatter.
+    /// Computing gravitational potential energy of the block under uniform gravity. The height of
+    /// the block is measured along the direction opposite to gravity, from the origin, and the
+    /// energy is zero when this height equals the reference height.
     ///
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    /// * `g` - gravitational acceleration, e.g. (0, 0, -9.81) for vertical gravity.
+    /// * `reference_height` - height associated to a zero potential energy.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(0.0, 0.0, 2.0)
+    ///     .get();
+    ///
+    /// let g = Vec3d::new(0.0, 0.0, -9.81);
+    ///
+    /// assert!((block.get_potential_energy(&g, 0.0) - 19.62).abs() < 1e-12);
+    /// assert!(block.get_potential_energy(&g, 2.0).abs() < 1e-12);
+    /// ```
+    pub fn get_potential_energy(&self, g: &Vec3d, reference_height: f64) -> f64
     {
-        for index in self.data_index.iter()
-        {
-            match *index {
-                0 => write!(f, " {:.*} ", self.decimal, self.block.position.coords.x).unwrap(),
-                1 => write!(f, " {:.*} ", self.decimal, self.block.position.coords.y).unwrap(),
-                2 => write!(f, " {:.*} ", self.decimal, self.block.position.coords.z).unwrap(),
-                3 => write!(f, " {:.*} ", self.decimal, self.block.velocity.coords.x).unwrap(),
-                4 => write!(f, " {:.*} ", self.decimal, self.block.velocity.coords.y).unwrap(),
-                5 => write!(f, " {:.*} ", self.decimal, self.block.velocity.coords.z).unwrap(),
-                _ => (),
-            };
-        }
-        Ok(())
+        let (p, g) = (&self.position.coords, &g.coords);
+        -self.mass * (g.x * p.x + g.y * p.y + g.z * p.z) - self.mass * g.norm() * reference_height
     }
-}
+
+    /// Computing principal inertia tensor of the block, expressed in the block frame. Degenerate
+    /// blocks, i.e. with a zero length, are associated to a zero tensor.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(12.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// let inertia = block.get_inertia_tensor();
+    ///
+    /// assert!((inertia[0][0] - 2.0).abs() < 1e-12);
+    /// assert!((inertia[1][1] - 2.0).abs() < 1e-12);
+    /// assert!((inertia[2][2] - 2.0).abs() < 1e-12);
+    /// assert!(inertia[0][1].abs() < 1e-12);
+    /// ```
+    pub fn get_inertia_tensor(&self) -> [[f64; 3]; 3]
+    {
+        let mut inertia = [[0.0; 3]; 3];
+        if self.get_volume() == 0.0 { return inertia; }
+
+        let [lx, ly, lz] = self.lengths;
+        inertia[0][0] = self.mass / 12.0 * (ly * ly + lz * lz);
+        inertia[1][1] = self.mass / 12.0 * (lx * lx + lz * lz);
+        inertia[2][2] = self.mass / 12.0 * (lx * lx + ly * ly);
+        inertia
+    }
+
+    /// Computing inverse of the principal inertia tensor of the block, expressed in the block
+    /// frame. Degenerate blocks, i.e. with a zero length or zero mass, are associated to a zero
+    /// tensor.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(12.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// let inv_inertia = block.get_inverse_inertia_tensor();
+    /// assert!((inv_inertia[0][0] - 0.5).abs() < 1e-12);
+    ///
+    /// let flat = BlockBuilder::new()
+    ///     .set_mass_density(12.0)
+    ///     .set_lengths(1., 1., 0.)
+    ///     .get();
+    ///
+    /// let inv_inertia = flat.get_inverse_inertia_tensor();
+    /// assert!(inv_inertia[0][0].abs() < 1e-12);
+    /// ```
+    pub fn get_inverse_inertia_tensor(&self) -> [[f64; 3]; 3]
+    {
+        let mut inv_inertia = self.get_inertia_tensor();
+        for (i, row) in inv_inertia.iter_mut().enumerate()
+        {
+            if row[i] > 0.0 { row[i] = 1.0 / row[i]; }
+        }
+        inv_inertia
+    }
+
+    /// Computing moment of inertia of the block about an axis passing through its center of mass,
+    /// i.e. `a^T I a` where `I` is the inertia tensor and `a` the normalized axis expressed in the
+    /// block frame. The axis is given in world frame and normalized internally, a zero axis being
+    /// associated to a zero moment.
+    ///
+    /// * `axis` - direction of the axis.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass(3.0)
+    ///     .set_lengths(1., 2., 3.)
+    ///     .get();
+    ///
+    /// let moment = block.moment_of_inertia_about_axis(&Vec3d::new(2.0, 0.0, 0.0));
+    ///
+    /// assert!((moment - 3.0 / 12.0 * (2.0 * 2.0 + 3.0 * 3.0)).abs() < 1e-12);
+    /// ```
+    pub fn moment_of_inertia_about_axis(&self, axis: &Vec3d) -> f64
+    {
+        let norm = axis.coords.norm();
+        if norm == 0.0 { return 0.0; }
+
+        let a = self.orientation.inverse_rotate(axis);
+        let inertia = self.get_inertia_tensor();
+        (inertia[0][0] * a.coords.x * a.coords.x
+            + inertia[1][1] * a.coords.y * a.coords.y
+            + inertia[2][2] * a.coords.z * a.coords.z) / (norm * norm)
+    }
+
+    /// Computing speed of the block, i.e. the norm of its velocity.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_velocity(3.0, 4.0, 0.0)
+    ///     .get();
+    ///
+    /// assert!((block.speed() - 5.0).abs() < 1e-12);
+    /// ```
+    pub fn speed(&self) -> f64
+    {
+        self.velocity.coords.norm()
+    }
+
+    /// Computing direction of motion of the block, i.e. its normalized velocity. None is returned
+    /// if the block is stationary.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_velocity(3.0, 4.0, 0.0)
+    ///     .get();
+    ///
+    /// let direction = block.direction().unwrap();
+    ///
+    /// assert!((direction.coords.x - 0.6).abs() < 1e-12);
+    /// assert!((direction.coords.y - 0.8).abs() < 1e-12);
+    /// assert!(direction.coords.z.abs() < 1e-12);
+    /// assert!(BlockBuilder::new().get().direction().is_none());
+    /// ```
+    pub fn direction(&self) -> Option<Vec3d>
+    {
+        let speed = self.speed();
+        if speed == 0.0 { return None; }
+        let v = &self.velocity.coords;
+        Some(Vec3d::new(v.x / speed, v.y / speed, v.z / speed))
+    }
+
+    /// Setting velocity of the block from a speed and a direction of motion. The direction is
+    /// normalized internally, the velocity being left unchanged if the direction is zero.
+    ///
+    /// * `speed` - speed of the block.
+    /// * `direction` - direction of motion of the block.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let mut block = BlockBuilder::new().get();
+    /// block.aim(5.0, &Vec3d::new(0.0, 0.0, 2.0));
+    ///
+    /// assert!(block.velocity.coords.x.abs() < 1e-12);
+    /// assert!(block.velocity.coords.y.abs() < 1e-12);
+    /// assert!((block.velocity.coords.z - 5.0).abs() < 1e-12);
+    ///
+    /// block.aim(1.0, &Vec3d::new(0.0, 0.0, 0.0));
+    /// assert!((block.velocity.coords.z - 5.0).abs() < 1e-12);
+    /// ```
+    pub fn aim(&mut self, speed: f64, direction: &Vec3d) -> &mut Self
+    {
+        let norm = direction.coords.norm();
+        if norm == 0.0 { return self; }
+        let (d, s) = (&direction.coords, speed / norm);
+        self.velocity = Vec3d::new(s * d.x, s * d.y, s * d.z);
+        self
+    }
+
+    /// Putting the block to sleep if its speed is below a threshold. Static blocks are never put to
+    /// sleep. Returns whether the block is asleep.
+    ///
+    /// * `lin_threshold` - threshold on the speed of the block.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::collision::*;
+    /// use rody::integrator::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_velocity(1e-3, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// assert!(!block.try_sleep(1e-4));
+    /// assert!(block.try_sleep(1e-2));
+    ///
+    /// // Sleeping blocks are not integrated.
+    /// ExplicitEuler::new().step(&mut block, 1.0);
+    /// assert!(block.position.coords.x.abs() < 1e-12);
+    ///
+    /// // Sleeping blocks are woken up on contact.
+    /// let mut other = BlockBuilder::new()
+    ///     .set_mass(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(-0.9, 0.0, 0.0)
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// resolve_elastic_collision(&mut other, &mut block);
+    ///
+    /// assert!(!block.asleep);
+    /// assert!((block.velocity.coords.x - 1.0).abs() < 1e-12);
+    ///
+    /// // Sleeping blocks resting on static blocks are left asleep.
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_velocity(0.0, 0.0, -1e-3)
+    ///     .get();
+    /// let mut floor = BlockBuilder::new()
+    ///     .set_lengths(10., 10., 1.)
+    ///     .set_initial_position(0.0, 0.0, -0.99)
+    ///     .set_static(true)
+    ///     .get();
+    ///
+    /// assert!(block.try_sleep(1e-2));
+    /// resolve_elastic_collision(&mut block, &mut floor);
+    ///
+    /// assert!(block.asleep);
+    /// assert!((block.velocity.coords.z + 1e-3).abs() < 1e-12);
+    ///
+    /// // Separating blocks do not wake sleeping blocks up.
+    /// let mut other = BlockBuilder::new()
+    ///     .set_mass(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(-0.9, 0.0, 0.0)
+    ///     .set_initial_velocity(-1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// resolve_elastic_collision(&mut other, &mut block);
+    ///
+    /// assert!(block.asleep);
+    /// ```
+    pub fn try_sleep(&mut self, lin_threshold: f64) -> bool
+    {
+        if !self.is_static && self.speed() < lin_threshold { self.asleep = true; }
+        self.asleep
+    }
+
+    /// Waking the block up, so that it is integrated and collided again.
+    ///
+    pub fn wake(&mut self)
+    {
+        self.asleep = false;
+    }
+
+    /// Computing linear momentum of the block.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(2.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_velocity(3.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let momentum = block.get_linear_momentum();
+    ///
+    /// assert!((momentum.coords.x - 6.0).abs() < 1e-12);
+    /// assert!(momentum.coords.y.abs() < 1e-12);
+    /// assert!(momentum.coords.z.abs() < 1e-12);
+    /// ```
+    pub fn get_linear_momentum(&self) -> Vec3d
+    {
+        let v = &self.velocity.coords;
+        Vec3d::new(self.mass * v.x, self.mass * v.y, self.mass * v.z)
+    }
+
+    /// Computing velocity of a point rigidly attached to the block, i.e. `v + w x (p - c)` where `v`
+    /// is the block velocity, `w` its angular velocity, `c` its center of mass and `x` denotes the
+    /// right-handed cross product.
+    ///
+    /// * `p` - point at which the velocity is computed.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// block.angular_velocity = Vec3d::new(0.0, 0.0, 1.0);
+    /// let v = block.velocity_at_point(&Pnt3d::new(1.0, 0.0, 0.0));
+    ///
+    /// assert!((v.coords.x - 1.0).abs() < 1e-12);
+    /// assert!((v.coords.y - 1.0).abs() < 1e-12);
+    /// assert!(v.coords.z.abs() < 1e-12);
+    /// ```
+    pub fn velocity_at_point(&self, p: &Pnt3d) -> Vec3d
+    {
+        let w = &self.angular_velocity.coords;
+        let r = [p.coords.x - self.position.coords.x, p.coords.y - self.position.coords.y, p.coords.z - self.position.coords.z];
+        Vec3d::new(
+            self.velocity.coords.x + w.y * r[2] - w.z * r[1],
+            self.velocity.coords.y + w.z * r[0] - w.x * r[2],
+            self.velocity.coords.z + w.x * r[1] - w.y * r[0],
+        )
+    }
+
+    /// Computing axis-aligned bounding box of the block. The orientation of the block is not
+    /// taken into account, i.e. the block is assumed to be axis-aligned.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(1., 0.5, 0.)
+    ///     .set_initial_position(1.0, 1.0, 1.0)
+    ///     .get();
+    ///
+    /// let aabb = block.get_aabb();
+    ///
+    /// assert!((aabb.min.coords.x - 0.5).abs() < 1e-12);
+    /// assert!((aabb.max.coords.x - 1.5).abs() < 1e-12);
+    /// assert!((aabb.min.coords.y - 0.75).abs() < 1e-12);
+    /// assert!((aabb.max.coords.y - 1.25).abs() < 1e-12);
+    /// assert!((aabb.min.coords.z - aabb.max.coords.z).abs() < 1e-12);
+    /// ```
+    pub fn get_aabb(&self) -> Aabb
+    {
+        let p = &self.position.coords;
+        let h = [0.5 * self.lengths[0].abs(), 0.5 * self.lengths[1].abs(), 0.5 * self.lengths[2].abs()];
+        Aabb::new(
+            Pnt3d::new(p.x - h[0], p.y - h[1], p.z - h[2]),
+            Pnt3d::new(p.x + h[0], p.y + h[1], p.z + h[2]),
+        )
+    }
+
+    /// Computing axis-aligned bounding box of the block accounting for its orientation, i.e. the
+    /// bounds of its corners, see `get_corners`. For axis-aligned blocks, it matches `get_aabb`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_orientation_euler(0.0, 0.0, 0.25 * std::f64::consts::PI)
+    ///     .get();
+    ///
+    /// let aabb = block.get_bounding_aabb();
+    /// let h = 0.5 * 2f64.sqrt();
+    ///
+    /// assert!((aabb.min.coords.x + h).abs() < 1e-12);
+    /// assert!((aabb.max.coords.y - h).abs() < 1e-12);
+    /// assert!((aabb.max.coords.z - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn get_bounding_aabb(&self) -> Aabb
+    {
+        let corners = self.get_corners();
+        let mut min = corners[0].clone();
+        let mut max = corners[0].clone();
+        for c in corners.iter().skip(1)
+        {
+            min = Pnt3d::new(min.coords.x.min(c.coords.x), min.coords.y.min(c.coords.y), min.coords.z.min(c.coords.z));
+            max = Pnt3d::new(max.coords.x.max(c.coords.x), max.coords.y.max(c.coords.y), max.coords.z.max(c.coords.z));
+        }
+        Aabb::new(min, max)
+    }
+
+    /// Computing bounding sphere of the block, returned as its center and its radius. The sphere
+    /// is centered on the block center of mass and its radius is half the block diagonal.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// let (center, radius) = block.get_bounding_sphere();
+    ///
+    /// assert!(center.coords.norm() < 1e-12);
+    /// assert!((radius - (3.0 as f64).sqrt() / 2.0).abs() < 1e-12);
+    /// ```
+    pub fn get_bounding_sphere(&self) -> (Pnt3d, f64)
+    {
+        let [lx, ly, lz] = self.lengths;
+        (self.position.clone(), 0.5 * (lx * lx + ly * ly + lz * lz).sqrt())
+    }
+
+    /// Transforming a point from world space to the local frame of the block, i.e. the frame
+    /// centered on the block center of mass and aligned with its orientation.
+    ///
+    /// * `p` - point in world space.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// extern crate rody;
+    ///
+    /// use mersh::base::*;
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(1.0, 2.0, 3.0)
+    ///     .set_orientation_euler(0.5, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let p = Pnt3d::new(1.5, 2.0, 3.0);
+    /// let local = block.world_to_local(&p);
+    /// let back = block.local_to_world(&local);
+    ///
+    /// assert!((local.coords.norm() - 0.5).abs() < 1e-12);
+    /// assert!((back.coords.x - p.coords.x).abs() < 1e-12);
+    /// assert!((back.coords.y - p.coords.y).abs() < 1e-12);
+    /// assert!((back.coords.z - p.coords.z).abs() < 1e-12);
+    /// ```
+    pub fn world_to_local(&self, p: &Pnt3d) -> Pnt3d
+    {
+        let (c, o) = (&p.coords, &self.position.coords);
+        let local = self.orientation.inverse_rotate(&Vec3d::new(c.x - o.x, c.y - o.y, c.z - o.z));
+        Pnt3d::new(local.coords.x, local.coords.y, local.coords.z)
+    }
+
+    /// Transforming a point from the local frame of the block to world space, see
+    /// `world_to_local`.
+    ///
+    /// * `p` - point in the local frame of the block.
+    ///
+    pub fn local_to_world(&self, p: &Pnt3d) -> Pnt3d
+    {
+        let c = &p.coords;
+        let offset = self.orientation.rotate(&Vec3d::new(c.x, c.y, c.z));
+        let mut world = self.position.clone();
+        world.coords.add_in(1.0, &offset.coords);
+        world
+    }
+
+    /// Computing the eight corners of the block, taking into account its orientation. The corner
+    /// of index `i` is located, in the block frame, on the positive side of the first direction if
+    /// bit 0 of `i` is set, of the second direction if bit 1 is set, and of the third direction if
+    /// bit 2 is set. Hence corner 0 is the (-, -, -) corner and corner 7 is the (+, +, +) corner.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// let corners = block.get_corners();
+    ///
+    /// assert!((corners[0].coords.x + 0.5).abs() < 1e-12);
+    /// assert!((corners[0].coords.y + 0.5).abs() < 1e-12);
+    /// assert!((corners[0].coords.z + 0.5).abs() < 1e-12);
+    /// assert!((corners[7].coords.x - 0.5).abs() < 1e-12);
+    /// assert!((corners[7].coords.y - 0.5).abs() < 1e-12);
+    /// assert!((corners[7].coords.z - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn get_corners(&self) -> [Pnt3d; 8]
+    {
+        let mut corners: [Pnt3d; 8] = Default::default();
+        for (i, corner) in corners.iter_mut().enumerate()
+        {
+            let sign = |bit: usize| if i & (1 << bit) != 0 { 0.5 } else { -0.5 };
+            let local = Pnt3d::new(sign(0) * self.lengths[0], sign(1) * self.lengths[1], sign(2) * self.lengths[2]);
+            *corner = self.local_to_world(&local);
+        }
+        corners
+    }
+
+    /// Computing triangulated surface of the block, returned as its vertices and the indexes of the
+    /// vertices of its triangles. Vertices are the corners of the block, see `get_corners`, and
+    /// triangles are oriented so that their normals point outward.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// let (vertices, triangles) = block.to_mesh();
+    ///
+    /// assert_eq!(vertices.len(), 8);
+    /// assert_eq!(triangles.len(), 12);
+    ///
+    /// for tri in triangles.iter() {
+    ///     let (a, b, c) = (&vertices[tri[0]].coords, &vertices[tri[1]].coords, &vertices[tri[2]].coords);
+    ///     let u = [b.x - a.x, b.y - a.y, b.z - a.z];
+    ///     let v = [c.x - a.x, c.y - a.y, c.z - a.z];
+    ///     let n = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+    ///     assert!(n[0] * a.x + n[1] * a.y + n[2] * a.z > 0.0);
+    /// }
+    /// ```
+    pub fn to_mesh(&self) -> (Vec<Pnt3d>, Vec<[usize; 3]>)
+    {
+        let mut triangles = Vec::with_capacity(2 * BLOCK_FACES.len());
+        for face in BLOCK_FACES.iter()
+        {
+            triangles.push([face[0], face[1], face[2]]);
+            triangles.push([face[0], face[2], face[3]]);
+        }
+        (self.get_corners().to_vec(), triangles)
+    }
+
+    /// Testing if a point lies inside the block. Points lying on a face of the block are considered
+    /// inside. As for `get_aabb`, the block is assumed to be axis-aligned. A flat block, i.e. with a
+    /// zero length, only contains the points lying exactly in its plane.
+    ///
+    /// * `p` - point to test.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// assert!(block.contains(&Pnt3d::new(0.1, -0.2, 0.3)));
+    /// assert!(block.contains(&Pnt3d::new(0.5, 0.0, 0.0)));
+    /// assert!(!block.contains(&Pnt3d::new(0.6, 0.0, 0.0)));
+    ///
+    /// let flat = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 0.)
+    ///     .get();
+    ///
+    /// assert!(flat.contains(&Pnt3d::new(0.1, 0.1, 0.0)));
+    /// assert!(!flat.contains(&Pnt3d::new(0.1, 0.1, 0.1)));
+    /// ```
+    pub fn contains(&self, p: &Pnt3d) -> bool
+    {
+        let aabb = self.get_aabb();
+        aabb.min.coords.x <= p.coords.x && p.coords.x <= aabb.max.coords.x
+            && aabb.min.coords.y <= p.coords.y && p.coords.y <= aabb.max.coords.y
+            && aabb.min.coords.z <= p.coords.z && p.coords.z <= aabb.max.coords.z
+    }
+
+    /// Testing if the block overlaps another block, using their axis-aligned bounding boxes.
+    /// Blocks that are only touching are not overlapping.
+    ///
+    /// * `other` - block to test overlap with.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let a = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// let b = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(0.5, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let c = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(1.5, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// assert!(a.overlaps(&b));
+    /// assert!(!a.overlaps(&c));
+    /// ```
+    pub fn overlaps(&self, other: &Block) -> bool
+    {
+        self.get_aabb().intersects(&other.get_aabb())
+    }
+
+    /// Testing if two oriented blocks overlap, using the separating axis theorem over the face
+    /// normals of both blocks and the cross products of their edge directions. As for `overlaps`,
+    /// blocks that are only touching are not overlapping, so that both tests agree for
+    /// axis-aligned blocks.
+    ///
+    /// * `other` - block to test overlap with.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let bar = |yaw: f64| BlockBuilder::new()
+    ///     .set_lengths(4., 0.2, 0.2)
+    ///     .set_orientation_euler(0.0, 0.0, yaw)
+    ///     .get();
+    ///
+    /// let cube = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(0.0, 1.5, 0.0)
+    ///     .get();
+    ///
+    /// // Bars only collide with the cube once rotated toward it.
+    /// assert!(!bar(0.0).overlaps_oriented(&cube));
+    /// assert!(bar(0.5 * std::f64::consts::PI).overlaps_oriented(&cube));
+    /// assert!(!bar(0.1).overlaps_oriented(&cube));
+    ///
+    /// // Axis-aligned blocks agree with the axis-aligned test.
+    /// let other = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(2.4, 0.0, 0.0)
+    ///     .get();
+    /// assert_eq!(bar(0.0).overlaps_oriented(&other), bar(0.0).overlaps(&other));
+    /// assert!(bar(0.0).overlaps_oriented(&other));
+    /// ```
+    pub fn overlaps_oriented(&self, other: &Block) -> bool
+    {
+        let axes = |block: &Block| {
+            let mut axes = [[0.0; 3]; 3];
+            for (i, axis) in axes.iter_mut().enumerate()
+            {
+                let mut e = [0.0; 3];
+                e[i] = 1.0;
+                let u = block.orientation.rotate(&Vec3d::new(e[0], e[1], e[2]));
+                *axis = [u.coords.x, u.coords.y, u.coords.z];
+            }
+            axes
+        };
+        let dot = |u: &[f64; 3], v: &[f64; 3]| u[0] * v[0] + u[1] * v[1] + u[2] * v[2];
+        let cross = |u: &[f64; 3], v: &[f64; 3]| [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+
+        let (a, b) = (axes(self), axes(other));
+        let ha = [0.5 * self.lengths[0].abs(), 0.5 * self.lengths[1].abs(), 0.5 * self.lengths[2].abs()];
+        let hb = [0.5 * other.lengths[0].abs(), 0.5 * other.lengths[1].abs(), 0.5 * other.lengths[2].abs()];
+        let (p, q) = (&self.position.coords, &other.position.coords);
+        let d = [q.x - p.x, q.y - p.y, q.z - p.z];
+
+        // Testing if an axis separates the blocks, degenerate axes never separating them.
+        let separates = |l: &[f64; 3]| {
+            let norm = dot(l, l).sqrt();
+            if norm < 1e-12 { return false; }
+            let ra: f64 = (0..3).map(|i| ha[i] * dot(&a[i], l).abs()).sum();
+            let rb: f64 = (0..3).map(|i| hb[i] * dot(&b[i], l).abs()).sum();
+            dot(&d, l).abs() >= ra + rb
+        };
+
+        for i in 0..3
+        {
+            if separates(&a[i]) || separates(&b[i]) { return false; }
+            for bj in b.iter()
+            {
+                if separates(&cross(&a[i], bj)) { return false; }
+            }
+        }
+        true
+    }
+
+    /// Computing distance between the centers of mass of two blocks.
+    ///
+    /// * `other` - block to compute distance to.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let a = BlockBuilder::new().get();
+    /// let b = BlockBuilder::new()
+    ///     .set_initial_position(3.0, 4.0, 0.0)
+    ///     .get();
+    ///
+    /// assert!((a.center_distance_to(&b) - 5.0).abs() < 1e-12);
+    /// ```
+    pub fn center_distance_to(&self, other: &Block) -> f64
+    {
+        let (p, q) = (&self.position.coords, &other.position.coords);
+        Vec3d::new(q.x - p.x, q.y - p.y, q.z - p.z).coords.norm()
+    }
+
+    /// Computing gap between the axis-aligned bounding boxes of two blocks, defined as the maximum
+    /// over each direction of the gap between the boxes along this direction. The gap is positive
+    /// when the blocks are separated, zero when they are touching and negative when they overlap.
+    ///
+    /// * `other` - block to compute gap to.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let a = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// let b = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(1.5, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let c = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(0.75, 0.5, 0.0)
+    ///     .get();
+    ///
+    /// assert!((a.surface_gap_to(&b) - 0.5).abs() < 1e-12);
+    /// assert!((a.surface_gap_to(&c) + 0.25).abs() < 1e-12);
+    /// ```
+    pub fn surface_gap_to(&self, other: &Block) -> f64
+    {
+        let (p, q) = (&self.position.coords, &other.position.coords);
+        let d = [(q.x - p.x).abs(), (q.y - p.y).abs(), (q.z - p.z).abs()];
+        let mut gap = f64::NEG_INFINITY;
+        for (i, di) in d.iter().enumerate()
+        {
+            gap = gap.max(di - 0.5 * (self.lengths[i].abs() + other.lengths[i].abs()));
+        }
+        gap
+    }
+
+    /// Translating block in place, its velocity is left untouched.
+    ///
+    /// * `d` - translation vector.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_initial_velocity(0.0, 1.0, 0.0)
+    ///     .get();
+    ///
+    /// block.translate(&Vec3d::new(1.0, 0.0, 0.0));
+    ///
+    /// assert!((block.position.coords.x - 1.0).abs() < 1e-12);
+    /// assert!((block.velocity.coords.y - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn translate(&mut self, d: &Vec3d) -> &mut Self
+    {
+        self.position.coords.add_in(1.0, &d.coords);
+        self
+    }
+
+    /// Computing translated copy of the block, its velocity is left untouched.
+    ///
+    /// * `d` - translation vector.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let block = BlockBuilder::new().get();
+    /// let moved = block.translated(&Vec3d::new(1.0, 0.0, 0.0));
+    ///
+    /// assert!(block.position.coords.norm() < 1e-12);
+    /// assert!((moved.position.coords.x - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn translated(&self, d: &Vec3d) -> Block
+    {
+        let mut block = self.clone();
+        block.translate(d);
+        block
+    }
+
+    /// Scaling the block, i.e. multiplying its lengths by the input factor and its mass by the
+    /// cube of the factor, so that its density is preserved. Scaling by a factor that is not
+    /// strictly positive is a no-op.
+    ///
+    /// * `factor` - scaling factor applied to the lengths of the block.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass_density(2.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// block.scale(2.0);
+    ///
+    /// assert!((block.mass - 16.0).abs() < 1e-12);
+    /// assert!((block.get_density() - 2.0).abs() < 1e-12);
+    /// assert!((block.lengths[0] - 2.0).abs() < 1e-12);
+    ///
+    /// block.scale(-1.0);
+    /// assert!((block.mass - 16.0).abs() < 1e-12);
+    /// ```
+    pub fn scale(&mut self, factor: f64)
+    {
+        if factor.is_nan() || factor <= 0.0 { return; }
+        for l in self.lengths.iter_mut() { *l *= factor; }
+        self.mass *= factor * factor * factor;
+    }
+
+    /// Reflecting the velocity of the block across a plane, i.e. mirroring it about the plane of
+    /// the input normal. The normal is normalized internally, reflecting about a zero normal is a
+    /// no-op.
+    ///
+    /// * `normal` - normal of the reflection plane.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// extern crate rody;
+    ///
+    /// use mersh::base::*;
+    /// use rody::block::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_initial_velocity(1.0, 2.0, 0.0)
+    ///     .get();
+    ///
+    /// block.reflect_velocity(&Vec3d::new(2.0, 0.0, 0.0));
+    ///
+    /// assert!((block.velocity.coords.x + 1.0).abs() < 1e-12);
+    /// assert!((block.velocity.coords.y - 2.0).abs() < 1e-12);
+    /// assert!(block.velocity.coords.z.abs() < 1e-12);
+    /// ```
+    pub fn reflect_velocity(&mut self, normal: &Vec3d)
+    {
+        let norm = normal.coords.norm();
+        if norm == 0.0 { return; }
+        let (n, v) = (&normal.coords, &self.velocity.coords);
+        let vn = (v.x * n.x + v.y * n.y + v.z * n.z) / (norm * norm);
+        self.velocity.coords.add_in(-2.0 * vn, &normal.coords);
+    }
+
+    /// Splitting the block into `nx * ny * nz` equal sub-blocks. Sub-blocks have the density of the
+    /// block, so that their masses sum to the mass of the block, and inherit all its other
+    /// properties. Each sub-block is centered on its cell, taking into account the orientation of
+    /// the block. No sub-block is created if one of the numbers of divisions is zero.
+    ///
+    /// * `nx` - number of divisions in the first direction of the block.
+    /// * `ny` - number of divisions in the second direction of the block.
+    /// * `nz` - number of divisions in the third direction of the block.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(3.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let blocks = block.split(2, 2, 2);
+    ///
+    /// assert_eq!(blocks.len(), 8);
+    /// assert!((blocks.iter().map(|b| b.mass).sum::<f64>() - block.mass).abs() < 1e-12);
+    /// assert!((blocks[0].get_density() - 3.0).abs() < 1e-12);
+    /// assert!((blocks[0].position.coords.x + 0.25).abs() < 1e-12);
+    /// assert!((blocks[7].position.coords.z - 0.25).abs() < 1e-12);
+    /// assert!((blocks[7].velocity.coords.x - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn split(&self, nx: usize, ny: usize, nz: usize) -> Vec<Block>
+    {
+        let n = [nx, ny, nz];
+        if n.contains(&0) { return Vec::new(); }
+
+        let lengths = [self.lengths[0] / nx as f64, self.lengths[1] / ny as f64, self.lengths[2] / nz as f64];
+        let center = |i: usize, d: usize| (i as f64 + 0.5) * lengths[d] - 0.5 * self.lengths[d];
+
+        let mut blocks = Vec::with_capacity(nx * ny * nz);
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    let mut block = self.clone();
+                    block.lengths = lengths;
+                    block.mass = self.mass / (nx * ny * nz) as f64;
+                    block.position = self.local_to_world(&Pnt3d::new(center(i, 0), center(j, 1), center(k, 2)));
+                    blocks.push(block);
+                }
+            }
+        }
+        blocks
+    }
+
+    /// Adding force to the force accumulated on the block. Accumulated forces are applied by
+    /// integrators, in addition to their own force field, and cleared at the end of each step.
+    ///
+    /// * `f` - force to add.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// extern crate rody;
+    ///
+    /// use mersh::base::*;
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass(2.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// block.add_force(&Vec3d::new(0.0, 0.0, -4.0));
+    /// block.add_force(&Vec3d::new(2.0, 0.0, 0.0));
+    /// SemiImplicitEuler::new().step(&mut block, 0.5);
+    ///
+    /// assert!((block.velocity.coords.x - 0.5).abs() < 1e-12);
+    /// assert!((block.velocity.coords.z + 1.0).abs() < 1e-12);
+    /// assert!((block.position.coords.z + 0.5).abs() < 1e-12);
+    /// assert!(block.force.coords.norm() < 1e-12);
+    /// ```
+    pub fn add_force(&mut self, f: &Vec3d)
+    {
+        self.force.coords.add_in(1.0, &f.coords);
+    }
+
+    /// Clearing force and torque accumulated on the block.
+    ///
+    pub fn clear_forces(&mut self)
+    {
+        self.force = Vec3d::default();
+        self.torque = Vec3d::default();
+    }
+
+    /// Adding torque to the torque accumulated on the block, expressed in world frame around the
+    /// center of mass of the block. As accumulated forces, accumulated torques are applied by
+    /// integrators and cleared at the end of each step.
+    ///
+    /// * `t` - torque to add.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// extern crate rody;
+    ///
+    /// use mersh::base::*;
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass_density(12.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// let euler = SemiImplicitEuler::new();
+    /// for i in 1..=10 {
+    ///     block.add_torque(&Vec3d::new(0.0, 0.0, 4.0));
+    ///     euler.step(&mut block, 0.1);
+    ///     assert!((block.angular_velocity.coords.z - 0.2 * i as f64).abs() < 1e-12);
+    /// }
+    /// assert!(block.torque.coords.norm() < 1e-12);
+    /// ```
+    pub fn add_torque(&mut self, t: &Vec3d)
+    {
+        self.torque.coords.add_in(1.0, &t.coords);
+    }
+
+    /// Computing angular acceleration of the block under its accumulated torque, expressed in world
+    /// frame, i.e. the torque multiplied by the inverse inertia tensor. Static blocks are not
+    /// accelerated.
+    ///
+    pub fn get_angular_acceleration(&self) -> Vec3d
+    {
+        if self.is_static { return Vec3d::default(); }
+        self.apply_inverse_inertia(&self.torque)
+    }
+
+    /// Multiplying a vector expressed in world frame by the inverse inertia tensor of the block.
+    ///
+    fn apply_inverse_inertia(&self, u: &Vec3d) -> Vec3d
+    {
+        let b = self.orientation.inverse_rotate(u);
+        let inv_inertia = self.get_inverse_inertia_tensor();
+        let a = Vec3d::new(inv_inertia[0][0] * b.coords.x, inv_inertia[1][1] * b.coords.y, inv_inertia[2][2] * b.coords.z);
+        self.orientation.rotate(&a)
+    }
+
+    /// Advancing orientation of the block over a time step using its angular velocity, expressed
+    /// in world frame, i.e. `q += 0.5 dt (0, w) q`. The orientation is normalized afterwards, so
+    /// that it remains a unit quaternion.
+    ///
+    /// * `dt` - time step.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_lengths(2., 1., 1.)
+    ///     .set_angular_velocity(0.0, 0.0, 0.5 * std::f64::consts::PI)
+    ///     .get();
+    ///
+    /// for _ in 0..1000 { block.integrate_orientation(1e-3); }
+    ///
+    /// // Quarter turn around z: the corner (1, 0.5, 0.5) is brought to (-0.5, 1, 0.5).
+    /// let corner = &block.get_corners()[7];
+    /// assert!((corner.coords.x + 0.5).abs() < 1e-6);
+    /// assert!((corner.coords.y - 1.0).abs() < 1e-6);
+    /// assert!((corner.coords.z - 0.5).abs() < 1e-6);
+    /// assert!((block.orientation.norm() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn integrate_orientation(&mut self, dt: f64)
+    {
+        let w = &self.angular_velocity.coords;
+        let dq = Quaternion::new(0.0, w.x, w.y, w.z).compose(&self.orientation);
+        let q = &mut self.orientation;
+        q.w += 0.5 * dt * dq.w;
+        q.v.coords.add_in(0.5 * dt, &dq.v.coords);
+        q.normalize();
+    }
+
+    /// Clamping the block inside an axis-aligned domain, i.e. shifting its position so that its
+    /// axis-aligned bounding box fits inside the domain. Blocks strictly larger than the domain
+    /// along a direction are centered in the domain along this direction. Optionally, velocity
+    /// components pushing the block out of the domain through a wall it was clamped on are zeroed,
+    /// as well as velocity components along directions in which the block is centered.
+    ///
+    /// * `min` - corner of the domain with minimal coordinates.
+    /// * `max` - corner of the domain with maximal coordinates.
+    /// * `zero_outward_velocity` - true for zeroing velocity components pushing the block out.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 6.)
+    ///     .set_initial_position(1.8, 0.0, 0.0)
+    ///     .set_initial_velocity(1.0, 2.0, 3.0)
+    ///     .get();
+    ///
+    /// let (min, max) = (Pnt3d::new(-2.0, -2.0, -2.0), Pnt3d::new(2.0, 2.0, 2.0));
+    /// let mut other = block.clone();
+    /// block.clamp_to_domain(&min, &max, true);
+    ///
+    /// assert!((block.position.coords.x - 1.5).abs() < 1e-12);
+    /// assert!(block.velocity.coords.x.abs() < 1e-12);
+    /// assert!((block.velocity.coords.y - 2.0).abs() < 1e-12);
+    /// assert!(block.position.coords.z.abs() < 1e-12);
+    /// assert!(block.velocity.coords.z.abs() < 1e-12);
+    ///
+    /// // Keeping velocity untouched.
+    /// other.clamp_to_domain(&min, &max, false);
+    ///
+    /// assert!((other.position.coords.x - 1.5).abs() < 1e-12);
+    /// assert!((other.velocity.coords.x - 1.0).abs() < 1e-12);
+    /// assert!((other.velocity.coords.z - 3.0).abs() < 1e-12);
+    ///
+    /// // Blocks exactly as large as the domain are left untouched.
+    /// let mut block = BlockBuilder::new()
+    ///     .set_lengths(4., 1., 1.)
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// block.clamp_to_domain(&min, &max, true);
+    ///
+    /// assert!(block.position.coords.x.abs() < 1e-12);
+    /// assert!((block.velocity.coords.x - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn clamp_to_domain(&mut self, min: &Pnt3d, max: &Pnt3d, zero_outward_velocity: bool)
+    {
+        self.shift_into_box(min, max, if zero_outward_velocity { Some(0.0) } else { None });
+    }
+
+    /// Shifting the block so that its axis-aligned bounding box fits inside a box, blocks strictly
+    /// larger than the box along a direction being centered along this direction. If a scaling
+    /// factor is given, velocity components pushing the block out through a wall it was shifted on
+    /// are scaled by this factor, and velocity components along centered directions are zeroed.
+    ///
+    /// * `min` - corner of the box with minimal coordinates.
+    /// * `max` - corner of the box with maximal coordinates.
+    /// * `outward_scale` - scaling factor of outward velocity components, if any.
+    ///
+    pub(crate) fn shift_into_box(&mut self, min: &Pnt3d, max: &Pnt3d, outward_scale: Option<f64>)
+    {
+        let min = [min.coords.x, min.coords.y, min.coords.z];
+        let max = [max.coords.x, max.coords.y, max.coords.z];
+        let mut p = [self.position.coords.x, self.position.coords.y, self.position.coords.z];
+        let mut v = [self.velocity.coords.x, self.velocity.coords.y, self.velocity.coords.z];
+
+        for i in 0..3
+        {
+            let h = 0.5 * self.lengths[i].abs();
+            if 2.0 * h > max[i] - min[i] {
+                p[i] = 0.5 * (min[i] + max[i]);
+                if outward_scale.is_some() { v[i] = 0.0; }
+            }
+            else if p[i] - h < min[i] {
+                p[i] = min[i] + h;
+                if let Some(scale) = outward_scale { if v[i] < 0.0 { v[i] *= scale; } }
+            }
+            else if p[i] + h > max[i] {
+                p[i] = max[i] - h;
+                if let Some(scale) = outward_scale { if v[i] > 0.0 { v[i] *= scale; } }
+            }
+        }
+        self.position = Pnt3d::new(p[0], p[1], p[2]);
+        self.velocity = Vec3d::new(v[0], v[1], v[2]);
+    }
+
+    /// Applying impulse to the block, i.e. adding the impulse divided by the mass of the block to
+    /// its velocity. Applying an impulse to a static block or to a block with zero mass is a no-op.
+    ///
+    /// * `j` - impulse to apply.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass_density(2.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// block.apply_impulse(&Vec3d::new(2.0, 0.0, 0.0));
+    ///
+    /// assert!((block.velocity.coords.x - 1.0).abs() < 1e-12);
+    /// assert!(block.velocity.coords.y.abs() < 1e-12);
+    /// assert!(block.velocity.coords.z.abs() < 1e-12);
+    /// ```
+    pub fn apply_impulse(&mut self, j: &Vec3d) -> &mut Self
+    {
+        self.velocity.coords.add_in(self.get_inverse_mass(), &j.coords);
+        self
+    }
+
+    /// Applying impulse to the block at a given point, changing both its linear velocity, see
+    /// `apply_impulse`, and its angular velocity by the inverse inertia tensor times `r x j`, where
+    /// `r` is the vector from the center of mass of the block to the point. The point is given in
+    /// world coordinates. Applying an impulse to a static block is a no-op.
+    ///
+    /// * `j` - impulse applied on the block.
+    /// * `p` - point of application of the impulse, in world coordinates.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass_density(12.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// // Tangential hit on the face x = 0.5, off-center.
+    /// block.apply_impulse_at_point(&Vec3d::new(0.0, 6.0, 0.0), &Pnt3d::new(0.5, 0.0, 0.0));
+    ///
+    /// assert!((block.velocity.coords.y - 0.5).abs() < 1e-12);
+    /// assert!((block.angular_velocity.coords.z - 1.5).abs() < 1e-12);
+    /// assert!(block.angular_velocity.coords.x.abs() < 1e-12);
+    /// assert!(block.angular_velocity.coords.y.abs() < 1e-12);
+    /// ```
+    pub fn apply_impulse_at_point(&mut self, j: &Vec3d, p: &Pnt3d) -> &mut Self
+    {
+        if self.is_static { return self; }
+
+        let (c, q) = (&self.position.coords, &p.coords);
+        let r = [q.x - c.x, q.y - c.y, q.z - c.z];
+        let k = &j.coords;
+        let l = Vec3d::new(r[1] * k.z - r[2] * k.y, r[2] * k.x - r[0] * k.z, r[0] * k.y - r[1] * k.x);
+        let w = self.apply_inverse_inertia(&l);
+        self.angular_velocity.coords.add_in(1.0, &w.coords);
+        self.apply_impulse(j)
+    }
+
+    /// Computing inverse mass of the block. Static blocks and blocks with zero mass are associated
+    /// to a zero inverse mass.
+    ///
+    pub fn get_inverse_mass(&self) -> f64
+    {
+        if self.is_static || self.mass == 0.0 { 0.0 } else { 1.0 / self.mass }
+    }
+
+    /// Computing intersection of a ray with the axis-aligned bounding box of the block, using the
+    /// slab method. The returned value is the smallest non-negative parameter `t` such that
+    /// `origin + t dir` lies on the block, hence rays starting inside the block return `t = 0`.
+    /// Nothing is returned if the ray misses the block.
+    ///
+    /// * `origin` - origin of the ray.
+    /// * `dir` - direction of the ray, not necessarily normalized.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// let t = block.ray_intersection(&Pnt3d::new(3.0, 0.0, 0.0), &Vec3d::new(-1.0, 0.0, 0.0));
+    /// assert!((t.unwrap() - 2.5).abs() < 1e-12);
+    ///
+    /// let t = block.ray_intersection(&Pnt3d::new(0.0, 0.0, 0.0), &Vec3d::new(-1.0, 0.0, 0.0));
+    /// assert!(t.unwrap().abs() < 1e-12);
+    ///
+    /// let t = block.ray_intersection(&Pnt3d::new(3.0, 1.0, 0.0), &Vec3d::new(-1.0, 0.0, 0.0));
+    /// assert!(t.is_none());
+    /// ```
+    pub fn ray_intersection(&self, origin: &Pnt3d, dir: &Vec3d) -> Option<f64>
+    {
+        let aabb = self.get_aabb();
+        let o = [origin.coords.x, origin.coords.y, origin.coords.z];
+        let d = [dir.coords.x, dir.coords.y, dir.coords.z];
+        let min = [aabb.min.coords.x, aabb.min.coords.y, aabb.min.coords.z];
+        let max = [aabb.max.coords.x, aabb.max.coords.y, aabb.max.coords.z];
+
+        let (mut t_min, mut t_max) = (0.0_f64, f64::INFINITY);
+        for i in 0..3
+        {
+            if d[i] == 0.0 {
+                // Parallel ray: it must already lie within the slab.
+                if o[i] < min[i] || o[i] > max[i] { return None; }
+            }
+            else {
+                let (t0, t1) = ((min[i] - o[i]) / d[i], (max[i] - o[i]) / d[i]);
+                t_min = t_min.max(t0.min(t1));
+                t_max = t_max.min(t0.max(t1));
+                if t_min > t_max { return None; }
+            }
+        }
+        Some(t_min)
+    }
+
+    /// Computing closest point to the input point on the surface of the axis-aligned bounding box
+    /// of the block. Exterior points are clamped onto the box, while interior points are pushed
+    /// onto the nearest face.
+    ///
+    /// * `p` - query point.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .get();
+    ///
+    /// let q = block.closest_point_on_surface(&Pnt3d::new(2.0, 0.25, -3.0));
+    /// assert!((q.coords.x - 0.5).abs() < 1e-12);
+    /// assert!((q.coords.y - 0.25).abs() < 1e-12);
+    /// assert!((q.coords.z + 0.5).abs() < 1e-12);
+    ///
+    /// let q = block.closest_point_on_surface(&Pnt3d::new(0.1, -0.4, 0.0));
+    /// assert!((q.coords.x - 0.1).abs() < 1e-12);
+    /// assert!((q.coords.y + 0.5).abs() < 1e-12);
+    /// assert!(q.coords.z.abs() < 1e-12);
+    /// ```
+    pub fn closest_point_on_surface(&self, p: &Pnt3d) -> Pnt3d
+    {
+        let aabb = self.get_aabb();
+        let min = [aabb.min.coords.x, aabb.min.coords.y, aabb.min.coords.z];
+        let max = [aabb.max.coords.x, aabb.max.coords.y, aabb.max.coords.z];
+        let mut q = [p.coords.x, p.coords.y, p.coords.z];
+
+        if self.contains(p) {
+            // Pushing interior point onto the face requiring the smallest displacement.
+            let (mut axis, mut value, mut dist) = (0, q[0], f64::INFINITY);
+            for i in 0..3
+            {
+                if q[i] - min[i] < dist { axis = i; value = min[i]; dist = q[i] - min[i]; }
+                if max[i] - q[i] < dist { axis = i; value = max[i]; dist = max[i] - q[i]; }
+            }
+            q[axis] = value;
+        }
+        else {
+            for i in 0..3 { q[i] = q[i].max(min[i]).min(max[i]); }
+        }
+        Pnt3d::new(q[0], q[1], q[2])
+    }
+
+    /// Computing key of the cell containing the center of mass of the block on a uniform grid, i.e.
+    /// the integer coordinates of the cell. Blocks whose centers lie in the same cell share the same
+    /// key, which can be used for hashing blocks. The cell length must be strictly positive, which
+    /// is checked in debug builds, otherwise keys are meaningless.
+    ///
+    /// * `cell` - length of the edges of the cells of the grid.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let a = BlockBuilder::new().set_initial_position(0.12, -0.31, 2.05).get();
+    /// let b = BlockBuilder::new().set_initial_position(0.18, -0.39, 2.01).get();
+    /// let c = BlockBuilder::new().set_initial_position(0.21, -0.39, 2.01).get();
+    ///
+    /// assert_eq!(a.quantized_key(0.1), (1, -4, 20));
+    /// assert_eq!(a.quantized_key(0.1), b.quantized_key(0.1));
+    /// assert!(a.quantized_key(0.1) != c.quantized_key(0.1));
+    /// ```
+    pub fn quantized_key(&self, cell: f64) -> (i64, i64, i64)
+    {
+        debug_assert!(cell > 0.0, "cell length must be strictly positive, got {}", cell);
+        let p = &self.position.coords;
+        ((p.x / cell).floor() as i64, (p.y / cell).floor() as i64, (p.z / cell).floor() as i64)
+    }
+
+    /// Testing if two blocks are approximately equal, i.e. if their mass, lengths, position,
+    /// velocity, orientation, angular velocity and material coefficients are equal component-wise
+    /// within a tolerance, and if both blocks are static or dynamic.
+    ///
+    /// * `other` - block to compare with.
+    /// * `tol` - tolerance on each component.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use mersh::base::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_velocity(-1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let perturbed = block.translated(&Vec3d::new(1e-14, 0.0, 0.0));
+    /// let moved = block.translated(&Vec3d::new(1e-3, 0.0, 0.0));
+    ///
+    /// assert!(block.approx_eq(&perturbed, 1e-12));
+    /// assert!(!block.approx_eq(&moved, 1e-12));
+    /// ```
+    pub fn approx_eq(&self, other: &Block, tol: f64) -> bool
+    {
+        let close = |a: f64, b: f64| (a - b).abs() <= tol;
+        let close_coords = |a: &Vec3d, b: &Vec3d| close(a.coords.x, b.coords.x)
+            && close(a.coords.y, b.coords.y) && close(a.coords.z, b.coords.z);
+
+        close(self.mass, other.mass)
+            && self.lengths.iter().zip(other.lengths.iter()).all(|(a, b)| close(*a, *b))
+            && close(self.position.coords.x, other.position.coords.x)
+            && close(self.position.coords.y, other.position.coords.y)
+            && close(self.position.coords.z, other.position.coords.z)
+            && close_coords(&self.velocity, &other.velocity)
+            && self.orientation.approx_eq(&other.orientation, tol)
+            && close_coords(&self.angular_velocity, &other.angular_velocity)
+            && close_coords(&self.force, &other.force)
+            && close_coords(&self.torque, &other.torque)
+            && close(self.restitution, other.restitution)
+            && close(self.friction, other.friction)
+            && self.is_static == other.is_static
+            && self.asleep == other.asleep
+    }
+
+    /// Blending two blocks linearly, i.e. computing `(1 - t) a + t b` for their mass, lengths,
+    /// position and velocity. All other properties are taken from the first block. The blending
+    /// parameter is not clamped, values outside of [0, 1] extrapolating linearly.
+    ///
+    /// * `a` - block obtained for `t = 0`.
+    /// * `b` - block obtained for `t = 1`.
+    /// * `t` - blending parameter.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let a = BlockBuilder::new()
+    ///     .set_mass(1.0)
+    ///     .set_lengths(1., 2., 3.)
+    ///     .set_initial_position(0.0, 0.0, 0.0)
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let b = BlockBuilder::new()
+    ///     .set_mass(3.0)
+    ///     .set_lengths(3., 2., 1.)
+    ///     .set_initial_position(2.0, 4.0, -2.0)
+    ///     .set_initial_velocity(3.0, 2.0, 0.0)
+    ///     .get();
+    ///
+    /// let c = Block::lerp(&a, &b, 0.5);
+    ///
+    /// assert!((c.mass - 2.0).abs() < 1e-12);
+    /// assert!(c.lengths.iter().all(|l| (l - 2.0).abs() < 1e-12));
+    /// assert!((c.position.coords.x - 1.0).abs() < 1e-12);
+    /// assert!((c.position.coords.y - 2.0).abs() < 1e-12);
+    /// assert!((c.position.coords.z + 1.0).abs() < 1e-12);
+    /// assert!((c.velocity.coords.x - 2.0).abs() < 1e-12);
+    /// assert!((c.velocity.coords.y - 1.0).abs() < 1e-12);
+    /// assert!(Block::lerp(&a, &b, 0.0).approx_eq(&a, 1e-12));
+    /// ```
+    pub fn lerp(a: &Block, b: &Block, t: f64) -> Block
+    {
+        let mix = |x: f64, y: f64| x + t * (y - x);
+        let (sa, sb) = (a.to_state(), b.to_state());
+        let mut block = a.clone();
+        block.mass = mix(a.mass, b.mass);
+        for i in 0..3 { block.lengths[i] = mix(a.lengths[i], b.lengths[i]); }
+        block.position = Pnt3d::new(mix(sa[0], sb[0]), mix(sa[1], sb[1]), mix(sa[2], sb[2]));
+        block.velocity = Vec3d::new(mix(sa[3], sb[3]), mix(sa[4], sb[4]), mix(sa[5], sb[5]));
+        block
+    }
+
+    /// Creating block from a flat state array `[px, py, pz, vx, vy, vz]`, following the ordering of
+    /// indices used by the block formatter. All other properties of the block are defaulted.
+    ///
+    /// * `state` - position and velocity of the block.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let state = [1.0, 2.0, 3.0, -1.0, -2.0, -3.0];
+    /// let block = Block::from_state(&state);
+    ///
+    /// assert!((block.position.coords.y - 2.0).abs() < 1e-12);
+    /// assert!((block.velocity.coords.z + 3.0).abs() < 1e-12);
+    /// assert_eq!(block.to_state(), state);
+    ///
+    /// let block: Block = state.into();
+    /// let back: [f64; 6] = block.into();
+    /// assert_eq!(back, state);
+    /// ```
+    pub fn from_state(state: &[f64; 6]) -> Block
+    {
+        Block{
+            position: Pnt3d::new(state[0], state[1], state[2]),
+            velocity: Vec3d::new(state[3], state[4], state[5]),
+            ..Block::default()
+        }
+    }
+
+    /// Getting flat state array `[px, py, pz, vx, vy, vz]` of the block, following the ordering of
+    /// indices used by the block formatter.
+    ///
+    pub fn to_state(&self) -> [f64; 6]
+    {
+        let p = &self.position.coords;
+        let v = &self.velocity.coords;
+        [p.x, p.y, p.z, v.x, v.y, v.z]
+    }
+
+    /// Creating formatter of current block instance. Unrecognized tokens of the data string are
+    /// ignored, see `try_format` for a strict version.
+    ///
+    /// * `data_str` - whitespace-separated tokens selecting the data to format: `px`, `py`, `pz`
+    ///   for position coordinates, `vx`, `vy`, `vz` for velocity coordinates, `p` for the whole
+    ///   position, `v` for the whole velocity and `_` for both, `m` for the mass, `vol` for the
+    ///   volume and `ke` for the kinetic energy. A token followed by `:n` is formatted using `n`
+    ///   decimals, e.g. `px:6`.
+    /// * `decimal` - number of decimals for formatting values without explicit decimals.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(2.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_velocity(-1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// assert_eq!(format!("{}", block.format("m ke", 2)), " 2.00  1.00 ");
+    /// assert_eq!(format!("{}", block.format("vx vol", 1)), " -1.0  1.0 ");
+    /// assert_eq!(format!("{}", block.format("vx:3 vy m:0", 1)), " -1.000  0.0  2 ");
+    /// ```
+    pub fn format(&self, data_str: &str, decimal: usize) -> BlockFormatter<'_>
+    {
+        BlockFormatter::new(self, BlockFormatter::parse_data_str(data_str), decimal)
+    }
+
+    /// Creating formatter of current block instance, failing on unrecognized tokens of the data
+    /// string.
+    ///
+    /// * `data_str` - whitespace-separated tokens selecting the data to format, see `format`.
+    /// * `decimal` - number of decimals for formatting values.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new().get();
+    ///
+    /// assert!(block.try_format("p vx", 3).is_ok());
+    /// assert_eq!(block.try_format("p vv", 3).unwrap_err(), FormatError::UnknownToken(String::from("vv")));
+    /// ```
+    pub fn try_format(&self, data_str: &str, decimal: usize) -> Result<BlockFormatter<'_>, FormatError>
+    {
+        Ok(BlockFormatter::new(self, BlockFormatter::try_parse_data_str(data_str)?, decimal))
+    }
+}
+
+/// Computing axis-aligned block enclosing the axis-aligned bounding boxes of two blocks. The
+/// enclosing block is massless and at rest, all its other properties being defaulted.
+///
+/// * `a` - first block to enclose.
+/// * `b` - second block to enclose.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+///
+/// let a = BlockBuilder::new()
+///     .set_lengths(1., 1., 1.)
+///     .get();
+///
+/// let b = BlockBuilder::new()
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_position(2.0, 1.0, 0.0)
+///     .get();
+///
+/// let block = bounding_block(&a, &b);
+///
+/// assert!((block.lengths[0] - 3.0).abs() < 1e-12);
+/// assert!((block.lengths[1] - 2.0).abs() < 1e-12);
+/// assert!((block.lengths[2] - 1.0).abs() < 1e-12);
+/// assert!((block.position.coords.x - 1.0).abs() < 1e-12);
+/// assert!((block.position.coords.y - 0.5).abs() < 1e-12);
+/// assert!(block.mass.abs() < 1e-12);
+/// ```
+pub fn bounding_block(a: &Block, b: &Block) -> Block
+{
+    let (aa, ab) = (a.get_aabb(), b.get_aabb());
+    let min = [aa.min.coords.x.min(ab.min.coords.x), aa.min.coords.y.min(ab.min.coords.y), aa.min.coords.z.min(ab.min.coords.z)];
+    let max = [aa.max.coords.x.max(ab.max.coords.x), aa.max.coords.y.max(ab.max.coords.y), aa.max.coords.z.max(ab.max.coords.z)];
+    Block{
+        lengths: [max[0] - min[0], max[1] - min[1], max[2] - min[2]],
+        position: Pnt3d::new(0.5 * (min[0] + max[0]), 0.5 * (min[1] + max[1]), 0.5 * (min[2] + max[2])),
+        ..Block::default()
+    }
+}
+
+impl From<[f64; 6]> for Block {
+    /// Creating block from a flat state array, see `Block::from_state`.
+    ///
+    fn from(state: [f64; 6]) -> Self
+    {
+        Block::from_state(&state)
+    }
+}
+
+impl From<Block> for [f64; 6] {
+    /// Getting flat state array of a block, see `Block::to_state`.
+    ///
+    fn from(block: Block) -> Self
+    {
+        block.to_state()
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of block internal data formatter.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+use std::fmt;
+use std::io;
+
+/// Names of the data that can be formatted, ordered by data index.
+const DATA_NAMES: [&str; 9] = ["px", "py", "pz", "vx", "vy", "vz", "m", "vol", "ke"];
+
+impl<'a> BlockFormatter<'a> {
+    /// Creating new formatter, using default separator and width.
+    ///
+    fn new(block: &'a Block, data_index: Vec<(u8, Option<usize>)>, decimal: usize) -> Self
+    {
+        BlockFormatter{ block, data_index, decimal, separator: None, width: 0, scientific: false }
+    }
+
+    /// Setting separator between formatted values. By default, each value is padded with a single
+    /// space on both sides.
+    ///
+    /// * `separator` - string inserted between formatted values.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_position(1.0, 2.0, 3.0)
+    ///     .get();
+    ///
+    /// assert_eq!(format!("{}", block.format("p", 1)), " 1.0  2.0  3.0 ");
+    /// assert_eq!(format!("{}", block.format("p", 1).set_separator("\t")), "1.0\t2.0\t3.0");
+    /// ```
+    pub fn set_separator(&mut self, separator: &str) -> &mut Self
+    {
+        self.separator = Some(String::from(separator));
+        self
+    }
+
+    /// Setting minimum width of formatted values, values are right-aligned.
+    ///
+    /// * `width` - minimum width of formatted values.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_position(1.0, -2.0, 3.0)
+    ///     .get();
+    ///
+    /// assert_eq!(format!("{}", block.format("p", 1).set_width(5)), "   1.0   -2.0    3.0 ");
+    /// ```
+    pub fn set_width(&mut self, width: usize) -> &mut Self
+    {
+        self.width = width;
+        self
+    }
+
+    /// Setting scientific notation for formatted values, in which case the number of decimals is
+    /// the number of significant digits of the mantissa, at least one digit being written.
+    ///
+    /// * `scientific` - true for using scientific notation, false for using fixed notation.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_velocity(1.5e-9, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// assert_eq!(format!("{}", block.format("vx", 3)), " 0.000 ");
+    /// assert_eq!(format!("{}", block.format("vx", 3).set_scientific(true)), " 1.50e-9 ");
+    /// ```
+    pub fn set_scientific(&mut self, scientific: bool) -> &mut Self
+    {
+        self.scientific = scientific;
+        self
+    }
+
+    /// Writing formatted data to an output stream, producing the same content as the display trait
+    /// without allocating an intermediate string.
+    ///
+    /// * `w` - output stream.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_velocity(-1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let mut buffer: Vec<u8> = Vec::new();
+    /// block.format("v", 1).write_to(&mut buffer).unwrap();
+    ///
+    /// assert_eq!(buffer, b" -1.0  0.0  0.0 ");
+    /// ```
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()>
+    {
+        write!(w, "{}", self)
+    }
+
+    /// Formatting selected data as a JSON object, whose keys are the names of the data. Since JSON
+    /// has no representation for them, NaN and infinite values are written as `null`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_position(1.0, 2.0, 3.0)
+    ///     .set_initial_velocity(-1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// assert_eq!(block.format("px vx", 2).format_json(), "{\"px\":1.00,\"vx\":-1.00}");
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_velocity(std::f64::NAN, std::f64::INFINITY, 0.0)
+    ///     .get();
+    ///
+    /// assert_eq!(block.format("v", 1).format_json(), "{\"vx\":null,\"vy\":null,\"vz\":0.0}");
+    /// ```
+    pub fn format_json(&self) -> String
+    {
+        let fields: Vec<String> = self.data_index.iter()
+            .map(|data| {
+                let value = self.get_formatted_value(data, 0);
+                let name = DATA_NAMES[data.0 as usize];
+                if value.value.is_finite() { format!("\"{}\":{}", name, value) } else { format!("\"{}\":null", name) }
+            })
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+
+    /// Formatting selected data as a comma-separated row, see `csv_header` for the matching header.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_position(1.0, 2.0, 3.0)
+    ///     .set_initial_velocity(-1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let row = block.format("p vx", 3).to_csv_row();
+    /// let values: Vec<f64> = row.split(',').map(|v| v.parse().unwrap()).collect();
+    ///
+    /// assert_eq!(BlockFormatter::csv_header("p vx"), "px,py,pz,vx");
+    /// assert_eq!(row, "1.000,2.000,3.000,-1.000");
+    /// assert_eq!(values, vec![1.0, 2.0, 3.0, -1.0]);
+    /// ```
+    pub fn to_csv_row(&self) -> String
+    {
+        let fields: Vec<String> = self.data_index.iter()
+            .map(|data| format!("{}", self.get_formatted_value(data, 0)))
+            .collect();
+        fields.join(",")
+    }
+
+    /// Creating comma-separated header matching the rows produced by `to_csv_row`, for the same
+    /// input data string.
+    ///
+    /// * `data_str` - data string used for creating formatters.
+    ///
+    pub fn csv_header(data_str: &str) -> String
+    {
+        let fields: Vec<&str> = BlockFormatter::parse_data_str(data_str).iter()
+            .map(|data| DATA_NAMES[data.0 as usize])
+            .collect();
+        fields.join(",")
+    }
+
+    /// Accessing formatted value of data associated to input data index.
+    ///
+    fn get_formatted_value(&self, data: &(u8, Option<usize>), width: usize) -> FormattedValue
+    {
+        FormattedValue{
+            value: self.get_value(data.0),
+            decimal: data.1.unwrap_or(self.decimal),
+            width,
+            scientific: self.scientific,
+        }
+    }
+
+    /// Accessing value of data associated to input data index.
+    ///
+    fn get_value(&self, index: u8) -> f64
+    {
+        match index {
+            0 => self.block.position.coords.x,
+            1 => self.block.position.coords.y,
+            2 => self.block.position.coords.z,
+            3 => self.block.velocity.coords.x,
+            4 => self.block.velocity.coords.y,
+            5 => self.block.velocity.coords.z,
+            6 => self.block.mass,
+            7 => self.block.get_volume(),
+            _ => self.block.get_kinetic_energy(),
+        }
+    }
+
+    /// Parsing input data string to data index, unrecognized tokens are ignored.
+    ///
+    fn parse_data_str(data_str: &str) -> Vec<(u8, Option<usize>)>
+    {
+        let mut data_index = Vec::new();
+        for s in data_str.split_whitespace()
+        {
+            BlockFormatter::parse_token(s, &mut data_index);
+        }
+        data_index
+    }
+
+    /// Parsing input data string to data index, failing on unrecognized tokens.
+    ///
+    fn try_parse_data_str(data_str: &str) -> Result<Vec<(u8, Option<usize>)>, FormatError>
+    {
+        let mut data_index = Vec::new();
+        for s in data_str.split_whitespace()
+        {
+            if !BlockFormatter::parse_token(s, &mut data_index) {
+                return Err(FormatError::UnknownToken(String::from(s)));
+            }
+        }
+        Ok(data_index)
+    }
+
+    /// Parsing a single token of a data string, returns false if the token is not recognized. A
+    /// token may end with `:n`, where `n` is the number of decimal used for the associated data.
+    ///
+    fn parse_token(token: &str, data_index: &mut Vec<(u8, Option<usize>)>) -> bool
+    {
+        let mut split = token.splitn(2, ':');
+        let name = split.next().unwrap_or("");
+        let decimal = match split.next() {
+            Some(d) => match d.parse::<usize>() { Ok(d) => Some(d), Err(_) => return false },
+            None => None,
+        };
+
+        let range = match &*name.to_lowercase() {
+            "_" => 0..6,
+            "p" => 0..3,
+            "v" => 3..6,
+            "px" => 0..1,
+            "py" => 1..2,
+            "pz" => 2..3,
+            "vx" => 3..4,
+            "vy" => 4..5,
+            "vz" => 5..6,
+            "m" => 6..7,
+            "vol" => 7..8,
+            "ke" => 8..9,
+            _ => return false,
+        };
+        for i in range { data_index.push((i, decimal)); }
+        true
+    }
+}
+
+impl<'a> fmt::Display for BlockFormatter<'a> {
+    /// Implementation of display trait for a block formatter.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        for (i, index) in self.data_index.iter().enumerate()
+        {
+            let value = self.get_formatted_value(index, self.width);
+            match self.separator {
+                Some(ref separator) => {
+                    if i > 0 { write!(f, "{}", separator)?; }
+                    write!(f, "{}", value)?;
+                },
+                None => write!(f, " {} ", value)?,
+            };
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FormattedValue {
+    /// Implementation of display trait for a single formatted value.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        if self.scientific { write!(f, "{:>w$.p$e}", self.value, w = self.width, p = self.decimal.saturating_sub(1)) }
+        else { write!(f, "{:>w$.p$}", self.value, w = self.width, p = self.decimal) }
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of block parsing.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+use std::str::FromStr;
+
+impl FromStr for Block {
+    type Err = ParseBlockError;
+
+    /// Parsing block from a compact string of whitespace separated `key=value` entries. Recognized
+    /// keys are `m` for the total mass, `l` for the lengths, `p` for the position and `v` for the
+    /// velocity, vector values being given as comma separated components. The parsed block is
+    /// validated as with `BlockBuilder::try_get`.
+    ///
+    /// * `s` - string to parse.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block: Block = "m=1.0 l=1,1,1 p=0,0,0 v=-1,0,0".parse().unwrap();
+    ///
+    /// assert!((block.mass - 1.0).abs() < 1e-12);
+    /// assert!((block.get_volume() - 1.0).abs() < 1e-12);
+    /// assert!((block.velocity.coords.x + 1.0).abs() < 1e-12);
+    ///
+    /// let err = "m=1.0 l=1,x,1".parse::<Block>().unwrap_err();
+    /// assert_eq!(err, ParseBlockError::InvalidNumber(String::from("l"), String::from("x")));
+    ///
+    /// let err = "m=1.0 q=1".parse::<Block>().unwrap_err();
+    /// assert_eq!(err, ParseBlockError::UnknownKey(String::from("q")));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let mut builder = BlockBuilder::new();
+        for entry in s.split_whitespace() {
+            let mut kv = entry.splitn(2, '=');
+            let key = kv.next().unwrap_or("");
+            let value = match kv.next() {
+                Some(value) => value,
+                None => return Err(ParseBlockError::MalformedEntry(String::from(entry))),
+            };
+
+            match key {
+                "m" => { builder.set_mass(parse_components(key, value, 1)?[0]); },
+                "l" => { let c = parse_components(key, value, 3)?; builder.set_lengths(c[0], c[1], c[2]); },
+                "p" => { let c = parse_components(key, value, 3)?; builder.set_initial_position(c[0], c[1], c[2]); },
+                "v" => { let c = parse_components(key, value, 3)?; builder.set_initial_velocity(c[0], c[1], c[2]); },
+                _ => return Err(ParseBlockError::UnknownKey(String::from(key))),
+            }
+        }
+        builder.try_get().map_err(ParseBlockError::InvalidBlock)
+    }
+}
+
+/// Parsing comma separated components of a value.
+///
+/// * `key` - key associated to the value, used for reporting errors.
+/// * `value` - value to parse.
+/// * `n` - expected number of components.
+///
+fn parse_components(key: &str, value: &str, n: usize) -> Result<Vec<f64>, ParseBlockError>
+{
+    let components = value.split(',')
+        .map(|s| s.trim().parse::<f64>().map_err(|_| ParseBlockError::InvalidNumber(String::from(key), String::from(s))))
+        .collect::<Result<Vec<f64>, ParseBlockError>>()?;
+    if components.len() != n { return Err(ParseBlockError::InvalidComponentCount(String::from(key), components.len())); }
+    Ok(components)
+}
+
+impl fmt::Display for Block {
+    /// Implementation of display trait for blocks, printing mass, lengths, position and velocity
+    /// on a single line, in the form accepted by `FromStr`. The precision of the formatter is used
+    /// as number of decimal, defaulting to 3.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass(2.0)
+    ///     .set_lengths(1., 0.5, 2.0)
+    ///     .set_initial_velocity(-1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let s = format!("{}", block);
+    /// assert!(s.contains("m=2.000"));
+    /// assert!(s.contains("l=1.000,0.500,2.000"));
+    /// assert!(s.contains("p=0.000,0.000,0.000"));
+    /// assert!(s.contains("v=-1.000,0.000,0.000"));
+    ///
+    /// let s = format!("{:.1}", block);
+    /// assert_eq!(s, "m=2.0 l=1.0,0.5,2.0 p=0.0,0.0,0.0 v=-1.0,0.0,0.0");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let d = f.precision().unwrap_or(3);
+        let l = &self.lengths;
+        let p = &self.position.coords;
+        let v = &self.velocity.coords;
+        write!(f, "m={:.*} l={:.*},{:.*},{:.*} p={:.*},{:.*},{:.*} v={:.*},{:.*},{:.*}",
+            d, self.mass, d, l[0], d, l[1], d, l[2], d, p.x, d, p.y, d, p.z, d, v.x, d, v.y, d, v.z)
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of block errors.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl fmt::Display for BuildError {
+    /// Implementation of display trait for block building errors.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match *self {
+            BuildError::NonPositiveLength(i, l) => write!(f, "length {} of block is not positive: {}", i, l),
+            BuildError::NegativeMass(m) => write!(f, "mass of block is negative: {}", m),
+            BuildError::NotANumber(name) => write!(f, "{} of block is not a number", name),
+            BuildError::InvalidRestitution(e) => write!(f, "restitution of block is not within [0, 1]: {}", e),
+            BuildError::NegativeFriction(c) => write!(f, "friction of block is negative: {}", c),
+        }
+    }
+}
+
+impl ::std::error::Error for BuildError {}
+
+impl fmt::Display for FormatError {
+    /// Implementation of display trait for block formatting errors.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match *self {
+            FormatError::UnknownToken(ref token) => write!(f, "unknown token in data string: {}", token),
+        }
+    }
+}
+
+impl ::std::error::Error for FormatError {}
+
+impl fmt::Display for ParseBlockError {
+    /// Implementation of display trait for block parsing errors.
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match *self {
+            ParseBlockError::MalformedEntry(ref entry) => write!(f, "entry is not of the form key=value: {}", entry),
+            ParseBlockError::UnknownKey(ref key) => write!(f, "unknown key in block string: {}", key),
+            ParseBlockError::InvalidNumber(ref key, ref value) => write!(f, "invalid number for key {}: {}", key, value),
+            ParseBlockError::InvalidComponentCount(ref key, n) => write!(f, "invalid number of components for key {}: {}", key, n),
+            ParseBlockError::InvalidBlock(ref err) => write!(f, "invalid block: {}", err),
+        }
+    }
+}
+
+impl ::std::error::Error for ParseBlockError {}
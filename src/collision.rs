@@ -0,0 +1,334 @@
+// Using base tools of mersh.
+use mersh::base::*;
+
+// Using blocks.
+use block::*;
+
+/// Data structure for defining contacts between two overlapping blocks.
+#[derive(Clone, Debug)]
+pub struct Contact {
+    /// Penetration depth of the blocks along the contact normal.
+    pub depth: f64,
+    /// Unit contact normal, pointing from the first block to the second one.
+    pub normal: Vec3d,
+    /// Approximate contact point, i.e. the center of the intersection of the bounding boxes.
+    pub point: Pnt3d,
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of collision responses.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+/// Resolving elastic collision between two overlapping blocks, by exchanging momentum along the
+/// collision normal so that both kinetic energy and linear momentum are conserved. The collision
+/// normal is the direction of minimal overlap of the axis-aligned bounding boxes of the blocks.
+/// Nothing is done if the blocks are not overlapping, if they are already separating along the
+/// normal, or if one of them has zero mass. Static blocks behave as if they had an infinite mass,
+/// so that a dynamic block fully bounces back on a static one. Pairs of blocks that are either
+/// static or asleep are skipped, and sleeping blocks are woken up when approaching awake ones.
+///
+/// * `a` - first block of the collision.
+/// * `b` - second block of the collision.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+/// use rody::collision::*;
+///
+/// let mut a = BlockBuilder::new()
+///     .set_mass_density(1.0)
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_velocity(1.0, 0.0, 0.0)
+///     .get();
+///
+/// let mut b = BlockBuilder::new()
+///     .set_mass_density(1.0)
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_position(0.9, 0.0, 0.0)
+///     .set_initial_velocity(-2.0, 0.0, 0.0)
+///     .get();
+///
+/// resolve_elastic_collision(&mut a, &mut b);
+///
+/// assert!((a.velocity.coords.x + 2.0).abs() < 1e-12);
+/// assert!((b.velocity.coords.x - 1.0).abs() < 1e-12);
+///
+/// // A light block bounces back on a much heavier one.
+/// let mut light = BlockBuilder::new()
+///     .set_mass(1.0)
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_velocity(1.0, 0.0, 0.0)
+///     .get();
+///
+/// let mut heavy = BlockBuilder::new()
+///     .set_mass(1e6)
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_position(0.9, 0.0, 0.0)
+///     .get();
+///
+/// resolve_elastic_collision(&mut light, &mut heavy);
+///
+/// assert!(light.velocity.coords.x < -0.99);
+/// assert!(heavy.velocity.coords.x.abs() < 1e-5);
+///
+/// // A block bounces back on a static one, which does not move.
+/// let mut block = BlockBuilder::new()
+///     .set_mass(1.0)
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_velocity(1.0, 0.0, 0.0)
+///     .get();
+///
+/// let mut wall = BlockBuilder::new()
+///     .set_mass(1.0)
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_position(0.9, 0.0, 0.0)
+///     .set_static(true)
+///     .get();
+///
+/// resolve_elastic_collision(&mut block, &mut wall);
+///
+/// assert!((block.velocity.coords.x + 1.0).abs() < 1e-12);
+/// assert!(wall.velocity.coords.norm() < 1e-12);
+/// ```
+pub fn resolve_elastic_collision(a: &mut Block, b: &mut Block)
+{
+    resolve_collision_with_restitution(a, b, 1.0);
+}
+
+/// Resolving collision between two overlapping blocks using their restitution coefficients. The
+/// restitution coefficient of the collision is the minimum of the coefficients of the blocks, and
+/// the post-collision relative normal velocity is `-e` times the pre-collision one, both linear
+/// momentum being conserved. A zero restitution leaves the blocks moving together along the
+/// normal. As for `resolve_elastic_collision`, nothing is done if the blocks are not overlapping,
+/// are already separating or if one of them has zero mass.
+///
+/// * `a` - first block of the collision.
+/// * `b` - second block of the collision.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+/// use rody::collision::*;
+///
+/// let collide = |e: f64| {
+///     let mut a = BlockBuilder::new()
+///         .set_mass_density(1.0)
+///         .set_lengths(1., 1., 1.)
+///         .set_initial_velocity(1.0, 0.0, 0.0)
+///         .set_restitution(e)
+///         .get();
+///     let mut b = BlockBuilder::new()
+///         .set_mass_density(1.0)
+///         .set_lengths(1., 1., 1.)
+///         .set_initial_position(0.9, 0.0, 0.0)
+///         .set_initial_velocity(-1.0, 0.0, 0.0)
+///         .set_restitution(e)
+///         .get();
+///     resolve_collision(&mut a, &mut b);
+///     (a.velocity.coords.x, b.velocity.coords.x)
+/// };
+///
+/// let (va, vb) = collide(0.0);
+/// assert!(va.abs() < 1e-12 && vb.abs() < 1e-12);
+///
+/// let (va, vb) = collide(0.5);
+/// assert!((va + 0.5).abs() < 1e-12 && (vb - 0.5).abs() < 1e-12);
+///
+/// let (va, vb) = collide(1.0);
+/// assert!((va + 1.0).abs() < 1e-12 && (vb - 1.0).abs() < 1e-12);
+/// ```
+pub fn resolve_collision(a: &mut Block, b: &mut Block)
+{
+    let e = a.restitution.min(b.restitution);
+    resolve_collision_with_restitution(a, b, e);
+}
+
+/// Resolving collision between two overlapping blocks using a restitution coefficient, i.e. the
+/// post-collision relative normal velocity is `-e` times the pre-collision one.
+///
+fn resolve_collision_with_restitution(a: &mut Block, b: &mut Block, e: f64)
+{
+    if (!a.is_static && a.mass <= 0.0) || (!b.is_static && b.mass <= 0.0) { return; }
+    // Skipping contacts between blocks that are all either static or asleep.
+    if (a.asleep || a.is_static) && (b.asleep || b.is_static) { return; }
+
+    let (wa, wb) = (a.get_inverse_mass(), b.get_inverse_mass());
+    if wa + wb == 0.0 { return; }
+
+    let n = match get_collision_normal(a, b) {
+        Some(n) => n,
+        None => return,
+    };
+
+    // Relative normal velocity, positive when blocks are approaching.
+    let (va, vb) = (&a.velocity.coords, &b.velocity.coords);
+    let u = (va.x - vb.x) * n.coords.x + (va.y - vb.y) * n.coords.y + (va.z - vb.z) * n.coords.z;
+    if u <= 0.0 { return; }
+
+    // Waking sleeping blocks up on approaching contact with awake blocks.
+    a.wake();
+    b.wake();
+
+    let j = (1.0 + e) * u / (wa + wb);
+    a.apply_impulse(&Vec3d::new(-j * n.coords.x, -j * n.coords.y, -j * n.coords.z));
+    b.apply_impulse(&Vec3d::new(j * n.coords.x, j * n.coords.y, j * n.coords.z));
+}
+
+/// Computing collision normal between two overlapping blocks, see `contact_info`. Nothing is
+/// returned if the blocks are not overlapping.
+///
+fn get_collision_normal(a: &Block, b: &Block) -> Option<Vec3d>
+{
+    contact_info(a, b).map(|contact| contact.normal)
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of contact detection.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+/// Computing contact between two overlapping blocks. The contact normal is the unit axis of minimal
+/// overlap of the axis-aligned bounding boxes of the blocks, see `Block::get_bounding_aabb`,
+/// pointing from the first block to the second one, and the penetration depth is the overlap along
+/// this axis. The contact point is approximated by the center of the intersection of the bounding
+/// boxes. Nothing is returned if the bounding boxes are not overlapping.
+///
+/// * `a` - first block of the contact.
+/// * `b` - second block of the contact.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+/// use rody::collision::*;
+///
+/// let a = BlockBuilder::new()
+///     .set_lengths(1., 1., 1.)
+///     .get();
+///
+/// let b = BlockBuilder::new()
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_position(0.2, -0.8, 0.1)
+///     .get();
+///
+/// let contact = contact_info(&a, &b).unwrap();
+///
+/// assert!((contact.depth - 0.2).abs() < 1e-12);
+/// assert!(contact.normal.coords.x.abs() < 1e-12);
+/// assert!((contact.normal.coords.y + 1.0).abs() < 1e-12);
+/// assert!(contact.normal.coords.z.abs() < 1e-12);
+/// assert!((contact.point.coords.x - 0.1).abs() < 1e-12);
+/// assert!((contact.point.coords.y + 0.4).abs() < 1e-12);
+/// assert!((contact.point.coords.z - 0.05).abs() < 1e-12);
+///
+/// let far = BlockBuilder::new()
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_position(2.0, 0.0, 0.0)
+///     .get();
+/// assert!(contact_info(&a, &far).is_none());
+/// ```
+pub fn contact_info(a: &Block, b: &Block) -> Option<Contact>
+{
+    let (ba, bb) = (a.get_bounding_aabb(), b.get_bounding_aabb());
+    if !ba.intersects(&bb) { return None; }
+
+    let (pa, pb) = (&a.position.coords, &b.position.coords);
+    let d = [pb.x - pa.x, pb.y - pa.y, pb.z - pa.z];
+    let amin = [ba.min.coords.x, ba.min.coords.y, ba.min.coords.z];
+    let amax = [ba.max.coords.x, ba.max.coords.y, ba.max.coords.z];
+    let bmin = [bb.min.coords.x, bb.min.coords.y, bb.min.coords.z];
+    let bmax = [bb.max.coords.x, bb.max.coords.y, bb.max.coords.z];
+
+    let mut axis = 0;
+    let mut min_overlap = f64::INFINITY;
+    for i in 0..3
+    {
+        let overlap = amax[i].min(bmax[i]) - amin[i].max(bmin[i]);
+        if overlap < min_overlap { axis = i; min_overlap = overlap; }
+    }
+
+    let mut n = [0.0; 3];
+    n[axis] = if d[axis] < 0.0 { -1.0 } else { 1.0 };
+
+    let point = Pnt3d::new(
+        0.5 * (ba.min.coords.x.max(bb.min.coords.x) + ba.max.coords.x.min(bb.max.coords.x)),
+        0.5 * (ba.min.coords.y.max(bb.min.coords.y) + ba.max.coords.y.min(bb.max.coords.y)),
+        0.5 * (ba.min.coords.z.max(bb.min.coords.z) + ba.max.coords.z.min(bb.max.coords.z)),
+    );
+    Some(Contact{ depth: min_overlap, normal: Vec3d::new(n[0], n[1], n[2]), point })
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of continuous collision detection.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+/// Computing time of impact between two moving blocks, by sweeping their axis-aligned bounding
+/// boxes along their relative velocity over a time step. The returned value is the fraction of
+/// the time step at which the blocks first overlap, zero if they are already overlapping. Nothing
+/// is returned if the blocks do not overlap during the time step.
+///
+/// * `a` - first moving block.
+/// * `b` - second moving block.
+/// * `dt` - time step.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+/// use rody::collision::*;
+///
+/// // Fast block crossing a thin wall within a single time step.
+/// let bullet = BlockBuilder::new()
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_position(-2.0, 0.0, 0.0)
+///     .set_initial_velocity(100.0, 0.0, 0.0)
+///     .get();
+///
+/// let wall = BlockBuilder::new()
+///     .set_lengths(0.1, 10., 10.)
+///     .set_static(true)
+///     .get();
+///
+/// let mut moved = bullet.clone();
+/// moved.position.coords.x += 100.0 * 0.1;
+/// assert!(!moved.overlaps(&wall));
+///
+/// let toi = time_of_impact(&bullet, &wall, 0.1).unwrap();
+/// assert!((toi - 0.145).abs() < 1e-12);
+///
+/// // Blocks moving away from each other never touch.
+/// let mut away = bullet.clone();
+/// away.velocity.coords.x = -100.0;
+/// assert!(time_of_impact(&away, &wall, 0.1).is_none());
+/// ```
+pub fn time_of_impact(a: &Block, b: &Block, dt: f64) -> Option<f64>
+{
+    if dt <= 0.0 { return if a.overlaps(b) { Some(0.0) } else { None }; }
+
+    let (ba, bb) = (a.get_aabb(), b.get_aabb());
+    let amin = [ba.min.coords.x, ba.min.coords.y, ba.min.coords.z];
+    let amax = [ba.max.coords.x, ba.max.coords.y, ba.max.coords.z];
+    let bmin = [bb.min.coords.x, bb.min.coords.y, bb.min.coords.z];
+    let bmax = [bb.max.coords.x, bb.max.coords.y, bb.max.coords.z];
+    let (va, vb) = (&a.velocity.coords, &b.velocity.coords);
+    let v = [va.x - vb.x, va.y - vb.y, va.z - vb.z];
+
+    // Intersecting time intervals of overlap along each axis, within the time step.
+    let (mut entry, mut exit) = (0.0, dt);
+    for i in 0..3
+    {
+        if v[i] == 0.0 {
+            if amax[i] <= bmin[i] || amin[i] >= bmax[i] { return None; }
+            continue;
+        }
+        let (t0, t1) = ((bmin[i] - amax[i]) / v[i], (bmax[i] - amin[i]) / v[i]);
+        let (t0, t1) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+        entry = f64::max(entry, t0);
+        exit = f64::min(exit, t1);
+    }
+
+    if entry < exit { Some(entry / dt) } else { None }
+}
@@ -0,0 +1,227 @@
+// Using base tools of mersh.
+use mersh::base::*;
+
+// Using blocks.
+use block::*;
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of force generators.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+/// Creating force field associated to uniform gravity, i.e. the force `mass * g` applied on each
+/// block. The returned force field can be used with any integrator accepting force fields.
+///
+/// * `g` - gravitational acceleration, e.g. (0, 0, -9.81) for vertical gravity.
+///
+/// # Examples
+/// ```
+/// extern crate mersh;
+/// use rody::block::*;
+/// use rody::force::*;
+/// use rody::integrator::*;
+/// use mersh::base::*;
+///
+/// let integrator = SemiImplicitEuler::with_force(gravity(Vec3d::new(0.0, 0.0, -9.81)));
+///
+/// let mut block = BlockBuilder::new()
+///     .set_mass_density(2.0)
+///     .set_lengths(1., 1., 1.)
+///     .get();
+///
+/// for _ in 0..100 { integrator.step(&mut block, 0.01); }
+///
+/// assert!((block.velocity.coords.z + 9.81).abs() < 1e-10);
+/// ```
+pub fn gravity(g: Vec3d) -> impl Fn(&Block) -> Vec3d
+{
+    move |block: &Block| Vec3d::new(block.mass * g.coords.x, block.mass * g.coords.y, block.mass * g.coords.z)
+}
+
+/// Computing Hooke's law force applied on a block by a spring attached to the center of another
+/// block, i.e. `-k (d - l) u` where `d` is the distance between the block centers, `l` the rest
+/// length of the spring and `u` the unit vector from the other block to the block. The force
+/// applied on the other block is the opposite one. A zero force is returned when both centers
+/// coincide.
+///
+/// * `block` - block on which the force is applied.
+/// * `other` - block to which the spring is attached.
+/// * `k` - stiffness of the spring.
+/// * `rest_length` - rest length of the spring.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+/// use rody::force::*;
+///
+/// let a = BlockBuilder::new().get();
+/// let b = BlockBuilder::new()
+///     .set_initial_position(3.0, 0.0, 0.0)
+///     .get();
+///
+/// // Stretched spring pulling blocks toward each other.
+/// let fa = spring_force(&a, &b, 2.0, 1.0);
+/// let fb = spring_force(&b, &a, 2.0, 1.0);
+///
+/// assert!((fa.coords.x - 4.0).abs() < 1e-12);
+/// assert!((fb.coords.x + 4.0).abs() < 1e-12);
+/// assert!(spring_force(&a, &a, 2.0, 1.0).coords.norm() < 1e-12);
+/// ```
+pub fn spring_force(block: &Block, other: &Block, k: f64, rest_length: f64) -> Vec3d
+{
+    let (p, q) = (&block.position.coords, &other.position.coords);
+    let u = Vec3d::new(p.x - q.x, p.y - q.y, p.z - q.z);
+    let d = u.coords.norm();
+    if d == 0.0 { return Vec3d::default(); }
+
+    let s = -k * (d - rest_length) / d;
+    Vec3d::new(s * u.coords.x, s * u.coords.y, s * u.coords.z)
+}
+
+/// Computing linear drag force applied on a block, i.e. `-c v` where `v` is the velocity of the
+/// block.
+///
+/// * `block` - block on which the force is applied.
+/// * `c` - drag coefficient.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+/// use rody::force::*;
+/// use rody::integrator::*;
+///
+/// let mut block = BlockBuilder::new()
+///     .set_mass(1.0)
+///     .set_lengths(1., 1., 1.)
+///     .set_initial_velocity(3.0, 4.0, 0.0)
+///     .get();
+///
+/// let euler = SemiImplicitEuler::new();
+/// let mut speed = block.speed();
+/// for _ in 0..10 {
+///     let drag = linear_drag(&block, 0.5);
+///     block.add_force(&drag);
+///     euler.step(&mut block, 0.1);
+///     assert!(block.speed() < speed);
+///     speed = block.speed();
+/// }
+/// ```
+pub fn linear_drag(block: &Block, c: f64) -> Vec3d
+{
+    let v = &block.velocity.coords;
+    Vec3d::new(-c * v.x, -c * v.y, -c * v.z)
+}
+
+/// Computing quadratic drag force applied on a block, i.e. `-c |v| v` where `v` is the velocity
+/// of the block.
+///
+/// * `block` - block on which the force is applied.
+/// * `c` - drag coefficient.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+/// use rody::force::*;
+///
+/// let block = BlockBuilder::new()
+///     .set_initial_velocity(3.0, 4.0, 0.0)
+///     .get();
+///
+/// let drag = quadratic_drag(&block, 0.1);
+///
+/// assert!((drag.coords.x + 1.5).abs() < 1e-12);
+/// assert!((drag.coords.y + 2.0).abs() < 1e-12);
+/// assert!(drag.coords.z.abs() < 1e-12);
+/// ```
+pub fn quadratic_drag(block: &Block, c: f64) -> Vec3d
+{
+    let v = &block.velocity.coords;
+    let s = -c * v.norm();
+    Vec3d::new(s * v.x, s * v.y, s * v.z)
+}
+
+/// Computing attraction applied on a block by a fixed point mass, i.e. `-s m r / |r|^3` where `s`
+/// is the strength of the attraction, e.g. the gravitational constant times the point mass, `m`
+/// the mass of the block and `r` the vector from the center to the block. For avoiding the
+/// singularity at the center, the distance is clamped from below by the radius of the bounding
+/// sphere of the block, and a zero force is returned when the block is centered on the point mass.
+///
+/// * `block` - block on which the force is applied.
+/// * `center` - position of the point mass.
+/// * `strength` - strength of the attraction.
+///
+/// # Examples
+/// ```
+/// extern crate mersh;
+/// use rody::block::*;
+/// use rody::force::*;
+/// use mersh::base::*;
+///
+/// let center = Pnt3d::new(0.0, 0.0, 0.0);
+/// let block = |x: f64| BlockBuilder::new()
+///     .set_mass(2.0)
+///     .set_lengths(0.1, 0.1, 0.1)
+///     .set_initial_position(x, 0.0, 0.0)
+///     .get();
+///
+/// let near = central_force(&block(2.0), &center, 3.0);
+/// let far = central_force(&block(4.0), &center, 3.0);
+///
+/// assert!((near.coords.x + 1.5).abs() < 1e-12);
+/// assert!((near.coords.x - 4.0 * far.coords.x).abs() < 1e-12);
+/// assert!(far.coords.y.abs() < 1e-12 && far.coords.z.abs() < 1e-12);
+/// assert!(central_force(&block(0.0), &center, 3.0).coords.norm() < 1e-12);
+/// ```
+pub fn central_force(block: &Block, center: &Pnt3d, strength: f64) -> Vec3d
+{
+    let (p, c) = (&block.position.coords, &center.coords);
+    let r = Vec3d::new(p.x - c.x, p.y - c.y, p.z - c.z);
+    let d = r.coords.norm();
+    if d == 0.0 { return Vec3d::default(); }
+
+    let clamped = d.max(block.get_bounding_sphere().1);
+    let s = -strength * block.mass / (clamped * clamped * d);
+    Vec3d::new(s * r.coords.x, s * r.coords.y, s * r.coords.z)
+}
+
+/// Computing buoyancy applied on a block by a fluid whose free surface is the horizontal plane at a
+/// given height along the third direction, i.e. `-rho V g` where `rho` is the density of the fluid
+/// and `V` the submerged volume of the block. The submerged volume is computed from the extent of
+/// the axis-aligned bounding box of the block along the third direction.
+///
+/// * `block` - block on which the force is applied.
+/// * `fluid_density` - density of the fluid.
+/// * `g` - gravitational acceleration, e.g. (0, 0, -9.81) for vertical gravity.
+/// * `surface_height` - height of the free surface of the fluid.
+///
+/// # Examples
+/// ```
+/// extern crate mersh;
+/// use rody::block::*;
+/// use rody::force::*;
+/// use mersh::base::*;
+///
+/// let g = Vec3d::new(0.0, 0.0, -10.0);
+/// let block = |z: f64| BlockBuilder::new()
+///     .set_lengths(1., 2., 1.)
+///     .set_initial_position(0.0, 0.0, z)
+///     .get();
+///
+/// let full = buoyancy(&block(-1.0), 1000.0, &g, 0.0);
+/// let half = buoyancy(&block(0.0), 1000.0, &g, 0.0);
+///
+/// assert!((full.coords.z - 20000.0).abs() < 1e-9);
+/// assert!((half.coords.z - 10000.0).abs() < 1e-9);
+/// assert!(buoyancy(&block(1.0), 1000.0, &g, 0.0).coords.norm() < 1e-12);
+/// ```
+pub fn buoyancy(block: &Block, fluid_density: f64, g: &Vec3d, surface_height: f64) -> Vec3d
+{
+    let aabb = block.get_aabb();
+    let (zmin, zmax) = (aabb.min.coords.z, aabb.max.coords.z);
+    let fraction = if zmax > zmin { ((surface_height - zmin) / (zmax - zmin)).clamp(0.0, 1.0) }
+        else if surface_height >= zmin { 1.0 } else { 0.0 };
+
+    let s = -fluid_density * fraction * block.get_volume().abs();
+    Vec3d::new(s * g.coords.x, s * g.coords.y, s * g.coords.z)
+}
@@ -0,0 +1,726 @@
+// Using base tools of mersh.
+use mersh::base::*;
+
+// Using blocks.
+use block::*;
+
+// Using time lines.
+use timeline::*;
+
+// Using trajectories.
+use trajectory::*;
+
+/// Type for defining force fields, i.e. forces applied on blocks as functions of their state.
+pub type ForceField = Box<dyn Fn(&Block) -> Vec3d>;
+
+/// Trait for defining time integrators of block motions.
+pub trait Integrator {
+    /// Advancing state of a block over a time step, applying the force accumulated on the block in
+    /// addition to the force field of the integrator. The angular velocity of the block is advanced
+    /// using its accumulated torque, then its orientation using its angular velocity, see
+    /// `Block::integrate_orientation`. The accumulated force and torque are cleared at the end of
+    /// the step, static and sleeping blocks being left untouched otherwise.
+    ///
+    /// * `block` - block to advance.
+    /// * `dt` - time step.
+    ///
+    fn step(&self, block: &mut Block, dt: f64);
+}
+
+/// Data structure for defining explicit Euler integrators, advancing positions and velocities
+/// using velocities and forces at the beginning of time steps.
+#[derive(Default)]
+pub struct ExplicitEuler {
+    /// Force field applied on blocks, no force is applied if not set.
+    force: Option<ForceField>,
+}
+
+/// Data structure for defining semi-implicit (symplectic) Euler integrators, advancing velocities
+/// first using forces at the beginning of time steps, then positions using the updated velocities.
+#[derive(Default)]
+pub struct SemiImplicitEuler {
+    /// Force field applied on blocks, no force is applied if not set.
+    force: Option<ForceField>,
+}
+
+/// Data structure for defining velocity Verlet integrators, advancing positions and velocities
+/// with second-order accuracy using forces at both the beginning and the end of time steps.
+#[derive(Default)]
+pub struct VelocityVerlet {
+    /// Force field applied on blocks, no force is applied if not set.
+    force: Option<ForceField>,
+}
+
+/// Data structure for defining classic fourth-order Runge-Kutta integrators, advancing both
+/// positions and velocities of blocks under a force field.
+#[derive(Default)]
+pub struct RungeKutta4 {
+    /// Force field applied on blocks, no force is applied if not set.
+    force: Option<ForceField>,
+}
+
+/// Data structure for defining adaptive Runge-Kutta-Fehlberg integrators, advancing positions and
+/// velocities of blocks with embedded fourth and fifth-order schemes, the difference between both
+/// being used for controlling the size of time steps.
+pub struct RungeKuttaFehlberg45 {
+    /// Force field applied on blocks, no force is applied if not set.
+    force: Option<ForceField>,
+    /// Tolerance on the local error of each accepted step.
+    tolerance: f64,
+}
+
+/// Helper class for advancing blocks with a fixed time step over frames of variable durations.
+/// Each frame runs an integer number of fixed steps, the remaining time being carried over to the
+/// next frame.
+#[derive(Clone, Debug)]
+pub struct FixedStepper {
+    /// Fixed time step.
+    dt: f64,
+    /// Time accumulated from previous frames and not yet simulated.
+    accumulator: f64,
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of force field helpers.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+/// Computing acceleration of a block under a force field and the force accumulated on the block,
+/// for a given position and velocity of the block. Blocks with zero mass are not accelerated.
+///
+/// * `force` - force field applied on the block.
+/// * `block` - block on which the force field is applied.
+/// * `x` - position of the block.
+/// * `v` - velocity of the block.
+///
+fn get_acceleration(force: &Option<ForceField>, block: &Block, x: &[f64; 3], v: &[f64; 3]) -> [f64; 3]
+{
+    if block.mass == 0.0 { return [0.0; 3]; }
+
+    let mut f = block.force.clone();
+    if let Some(ref force) = *force {
+        let mut state = block.clone();
+        state.position = Pnt3d::new(x[0], x[1], x[2]);
+        state.velocity = Vec3d::new(v[0], v[1], v[2]);
+        f.coords.add_in(1.0, &force(&state).coords);
+    }
+    [f.coords.x / block.mass, f.coords.y / block.mass, f.coords.z / block.mass]
+}
+
+/// Advancing angular velocity of a block using its accumulated torque, then its orientation using
+/// the updated angular velocity.
+///
+/// * `block` - block to advance.
+/// * `dt` - time step.
+///
+fn advance_rotation(block: &mut Block, dt: f64)
+{
+    let alpha = block.get_angular_acceleration();
+    block.angular_velocity.coords.add_in(dt, &alpha.coords);
+    block.integrate_orientation(dt);
+}
+
+/// Computing `a + t b` for arrays of coordinates.
+///
+fn axpy(a: &[f64; 3], t: f64, b: &[f64; 3]) -> [f64; 3]
+{
+    [a[0] + t * b[0], a[1] + t * b[1], a[2] + t * b[2]]
+}
+
+/// Computing `x + h sum(c_i k_i)` for arrays of coordinates.
+///
+fn combine(x: &[f64; 3], h: f64, c: &[f64], k: &[[f64; 3]]) -> [f64; 3]
+{
+    let mut y = *x;
+    for (ci, ki) in c.iter().zip(k.iter()) { y = axpy(&y, h * ci, ki); }
+    y
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of simulation drivers.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+/// Advancing block over every time of a time line, each step using the time step of the time line
+/// from the current time. The state of the block is recorded at each time of the time line before
+/// stepping, and after the last step unless its time step is zero, e.g. at the last time of closed
+/// time lines. On return, the block is in its final state.
+///
+/// * `block` - block to advance.
+/// * `timeline` - time line driving the simulation.
+/// * `integrator` - integrator used for advancing the block.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+/// use rody::integrator::*;
+/// use rody::timeline::*;
+///
+/// let mut block = BlockBuilder::new()
+///     .set_initial_velocity(2.0, 0.0, -1.0)
+///     .get();
+///
+/// let trajectory = run(&mut block, RegularTimeLine::new(0.0, 1.0, 10), &ExplicitEuler::new());
+///
+/// assert!((block.position.coords.x - 2.0).abs() < 1e-12);
+/// assert!((block.position.coords.z + 1.0).abs() < 1e-12);
+/// assert_eq!(trajectory.len(), 11);
+///
+/// // Closed time lines end at their last time.
+/// let mut block = BlockBuilder::new()
+///     .set_initial_velocity(1.0, 0.0, 0.0)
+///     .get();
+///
+/// let trajectory = run(&mut block, RegularTimeLine::new_inclusive(0.0, 1.0, 10), &ExplicitEuler::new());
+///
+/// assert!((block.position.coords.x - 1.0).abs() < 1e-12);
+/// assert_eq!(trajectory.len(), 11);
+/// assert_eq!(trajectory.iter().last().unwrap().time, 1.0);
+///
+/// let mut block = BlockBuilder::new()
+///     .set_initial_velocity(1.0, 0.0, 0.0)
+///     .get();
+///
+/// let trajectory = run(&mut block, IrregularTimeLine::new(vec![0.0, 0.5, 1.0]), &ExplicitEuler::new());
+///
+/// assert!((block.position.coords.x - 1.0).abs() < 1e-12);
+/// assert_eq!(trajectory.len(), 3);
+/// ```
+pub fn run<T: TimeLine>(block: &mut Block, mut timeline: T, integrator: &dyn Integrator) -> Trajectory
+{
+    let mut trajectory = Trajectory::new();
+    let mut final_time = None;
+    loop
+    {
+        let dt = timeline.time_step();
+        let time = match timeline.advance() {
+            Some(time) => time,
+            None => break,
+        };
+        trajectory.record(time, block);
+        if dt > 0.0 {
+            integrator.step(block, dt);
+            final_time = Some(time + dt);
+        }
+        else { final_time = None; }
+    }
+    if let Some(time) = final_time { trajectory.record(time, block); }
+    trajectory
+}
+
+impl FixedStepper {
+    /// Creating new fixed stepper, with an empty accumulator.
+    ///
+    /// * `dt` - fixed time step.
+    ///
+    pub fn new(dt: f64) -> Self
+    {
+        FixedStepper{ dt, accumulator: 0.0 }
+    }
+
+    /// Accessing time accumulated from previous frames and not yet simulated.
+    ///
+    pub fn accumulator(&self) -> f64
+    {
+        self.accumulator
+    }
+
+    /// Advancing block over a frame, running as many fixed steps as fit in the accumulated time.
+    /// Accumulated times within round-off of a whole number of steps are fully simulated. Nothing
+    /// is simulated if the fixed time step is not strictly positive. The number of steps run is
+    /// returned.
+    ///
+    /// * `block` - block to advance.
+    /// * `frame_time` - elapsed time of the frame.
+    /// * `integrator` - integrator used for advancing the block.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let mut stepper = FixedStepper::new(0.01);
+    /// let euler = ExplicitEuler::new();
+    ///
+    /// assert_eq!(stepper.advance(&mut block, 0.025, &euler), 2);
+    /// assert!((stepper.accumulator() - 0.005).abs() < 1e-12);
+    ///
+    /// assert_eq!(stepper.advance(&mut block, 0.025, &euler), 3);
+    /// assert!(stepper.accumulator().abs() < 1e-12);
+    /// assert!((block.position.coords.x - 0.05).abs() < 1e-12);
+    /// ```
+    pub fn advance(&mut self, block: &mut Block, frame_time: f64, integrator: &dyn Integrator) -> usize
+    {
+        if self.dt <= 0.0 { return 0; }
+
+        self.accumulator += frame_time;
+        let nstep = (self.accumulator / self.dt + 1e-9).floor().max(0.0) as usize;
+        for _ in 0..nstep { integrator.step(block, self.dt); }
+        self.accumulator = (self.accumulator - nstep as f64 * self.dt).max(0.0);
+        nstep
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of adaptive Runge-Kutta-Fehlberg integrator.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+/// Nodes of the Runge-Kutta-Fehlberg tableau, as coefficients of the previous stages.
+const RKF45_A: [&[f64]; 6] = [
+    &[],
+    &[1.0 / 4.0],
+    &[3.0 / 32.0, 9.0 / 32.0],
+    &[1932.0 / 2197.0, -7200.0 / 2197.0, 7296.0 / 2197.0],
+    &[439.0 / 216.0, -8.0, 3680.0 / 513.0, -845.0 / 4104.0],
+    &[-8.0 / 27.0, 2.0, -3544.0 / 2565.0, 1859.0 / 4104.0, -11.0 / 40.0],
+];
+
+/// Weights of the fifth-order scheme of the Runge-Kutta-Fehlberg tableau.
+const RKF45_B5: [f64; 6] = [16.0 / 135.0, 0.0, 6656.0 / 12825.0, 28561.0 / 56430.0, -9.0 / 50.0, 2.0 / 55.0];
+
+/// Weights of the fourth-order scheme of the Runge-Kutta-Fehlberg tableau.
+const RKF45_B4: [f64; 6] = [25.0 / 216.0, 0.0, 1408.0 / 2565.0, 2197.0 / 4104.0, -1.0 / 5.0, 0.0];
+
+impl Default for RungeKuttaFehlberg45 {
+    /// Default integrator applies no force on blocks, with a tolerance of 1e-6.
+    ///
+    fn default() -> Self
+    {
+        RungeKuttaFehlberg45{ force: None, tolerance: 1e-6 }
+    }
+}
+
+impl RungeKuttaFehlberg45 {
+    /// Creating new integrator, without any force applied on blocks.
+    ///
+    pub fn new() -> Self
+    {
+        RungeKuttaFehlberg45::default()
+    }
+
+    /// Creating new integrator, applying a force field on blocks.
+    ///
+    /// * `force` - force field applied on blocks, as a function of their state.
+    ///
+    pub fn with_force<F: Fn(&Block) -> Vec3d + 'static>(force: F) -> Self
+    {
+        RungeKuttaFehlberg45{ force: Some(Box::new(force)), ..RungeKuttaFehlberg45::default() }
+    }
+
+    /// Setting tolerance on the local error of each accepted step, i.e. the maximal difference
+    /// between the fourth and fifth-order estimates of positions and velocities.
+    ///
+    /// * `tolerance` - tolerance on the local error.
+    ///
+    pub fn set_tolerance(&mut self, tolerance: f64) -> &mut Self
+    {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Advancing block over a single adaptive step, starting from a proposed time step. The step is
+    /// shrunk until the local error is within tolerance, then accepted. The accepted time step and
+    /// the proposed time step for the next step are returned. Static and sleeping blocks are left
+    /// untouched, the proposed time step being accepted.
+    ///
+    /// * `block` - block to advance.
+    /// * `dt` - proposed time step.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use mersh::base::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let weak = RungeKuttaFehlberg45::with_force(|b: &Block| Vec3d::new(-b.position.coords.x, 0.0, 0.0));
+    /// let strong = RungeKuttaFehlberg45::with_force(|b: &Block| Vec3d::new(-1e4 * b.position.coords.x, 0.0, 0.0));
+    ///
+    /// let (weak_dt, _) = weak.adaptive_step(&mut block.clone(), 0.1);
+    /// let (strong_dt, _) = strong.adaptive_step(&mut block.clone(), 0.1);
+    ///
+    /// assert!(strong_dt < 0.1 * weak_dt);
+    /// ```
+    pub fn adaptive_step(&self, block: &mut Block, dt: f64) -> (f64, f64)
+    {
+        if block.is_static || block.asleep { block.clear_forces(); return (dt, dt); }
+        let steps = self.advance(block, dt);
+        block.clear_forces();
+        steps
+    }
+
+    /// Advancing block over a single adaptive step, without clearing forces accumulated on the
+    /// block.
+    ///
+    fn advance(&self, block: &mut Block, dt: f64) -> (f64, f64)
+    {
+        let x = [block.position.coords.x, block.position.coords.y, block.position.coords.z];
+        let v = [block.velocity.coords.x, block.velocity.coords.y, block.velocity.coords.z];
+        let min_dt = 1e-12 * dt.abs();
+
+        let mut h = dt;
+        loop
+        {
+            // Evaluating stages of the tableau.
+            let mut kx = [[0.0; 3]; 6];
+            let mut kv = [[0.0; 3]; 6];
+            for (i, a) in RKF45_A.iter().enumerate()
+            {
+                let (xi, vi) = (combine(&x, h, a, &kx), combine(&v, h, a, &kv));
+                kx[i] = vi;
+                kv[i] = get_acceleration(&self.force, block, &xi, &vi);
+            }
+
+            let (x5, v5) = (combine(&x, h, &RKF45_B5, &kx), combine(&v, h, &RKF45_B5, &kv));
+            let (x4, v4) = (combine(&x, h, &RKF45_B4, &kx), combine(&v, h, &RKF45_B4, &kv));
+            let error = (0..3).map(|i| f64::max((x5[i] - x4[i]).abs(), (v5[i] - v4[i]).abs())).fold(0.0, f64::max);
+
+            // Accepting step within tolerance, or shrinking it.
+            if error <= self.tolerance || h.abs() <= min_dt
+            {
+                block.position = Pnt3d::new(x5[0], x5[1], x5[2]);
+                block.velocity = Vec3d::new(v5[0], v5[1], v5[2]);
+                advance_rotation(block, h);
+                let factor = if error > 0.0 { 0.9 * (self.tolerance / error).powf(0.2) } else { 5.0 };
+                return (h, h * factor.clamp(0.2, 5.0));
+            }
+            h *= f64::max(0.9 * (self.tolerance / error).powf(0.25), 0.1);
+        }
+    }
+}
+
+impl Integrator for RungeKuttaFehlberg45 {
+    /// Advancing block position and velocity over a time step, using as many adaptive steps as
+    /// required for keeping the local error of each of them within tolerance.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use mersh::base::*;
+    ///
+    /// // Stiff spring of angular frequency 100.
+    /// let rkf = RungeKuttaFehlberg45::with_force(|b: &Block| Vec3d::new(-1e4 * b.position.coords.x, 0.0, 0.0));
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// rkf.step(&mut block, 0.1);
+    ///
+    /// assert!((block.position.coords.x - 10f64.cos()).abs() < 1e-4);
+    /// ```
+    fn step(&self, block: &mut Block, dt: f64)
+    {
+        if block.is_static || block.asleep { block.clear_forces(); return; }
+
+        let (mut t, mut h) = (0.0, dt);
+        while dt - t > 1e-12 * dt.abs()
+        {
+            let (accepted, next) = self.advance(block, h.min(dt - t));
+            t += accepted;
+            h = next;
+        }
+        block.clear_forces();
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of explicit Euler integrator.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl ExplicitEuler {
+    /// Creating new integrator, without any force applied on blocks.
+    ///
+    pub fn new() -> Self
+    {
+        ExplicitEuler::default()
+    }
+
+    /// Creating new integrator, applying a force field on blocks.
+    ///
+    /// * `force` - force field applied on blocks, as a function of their state.
+    ///
+    pub fn with_force<F: Fn(&Block) -> Vec3d + 'static>(force: F) -> Self
+    {
+        ExplicitEuler{ force: Some(Box::new(force)) }
+    }
+}
+
+impl Integrator for ExplicitEuler {
+    /// Advancing block position using its current velocity, and block velocity using the current
+    /// force.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_initial_position(1.0, 0.0, 0.0)
+    ///     .set_initial_velocity(-1.0, 2.0, 0.0)
+    ///     .get();
+    ///
+    /// let euler = ExplicitEuler::new();
+    /// for _ in 0..10 { euler.step(&mut block, 0.1); }
+    ///
+    /// assert!(block.position.coords.x.abs() < 1e-12);
+    /// assert!((block.position.coords.y - 2.0).abs() < 1e-12);
+    /// assert!(block.position.coords.z.abs() < 1e-12);
+    /// ```
+    fn step(&self, block: &mut Block, dt: f64)
+    {
+        if block.is_static || block.asleep { block.clear_forces(); return; }
+
+        let x = [block.position.coords.x, block.position.coords.y, block.position.coords.z];
+        let v = [block.velocity.coords.x, block.velocity.coords.y, block.velocity.coords.z];
+        let a = get_acceleration(&self.force, block, &x, &v);
+
+        block.position.coords.add_in(dt, &block.velocity.coords);
+        block.velocity.coords.add_in(dt, &Vec3d::new(a[0], a[1], a[2]).coords);
+        advance_rotation(block, dt);
+        block.clear_forces();
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of semi-implicit Euler integrator.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl SemiImplicitEuler {
+    /// Creating new integrator, without any force applied on blocks.
+    ///
+    pub fn new() -> Self
+    {
+        SemiImplicitEuler::default()
+    }
+
+    /// Creating new integrator, applying a force field on blocks.
+    ///
+    /// * `force` - force field applied on blocks, as a function of their state.
+    ///
+    pub fn with_force<F: Fn(&Block) -> Vec3d + 'static>(force: F) -> Self
+    {
+        SemiImplicitEuler{ force: Some(Box::new(force)) }
+    }
+}
+
+impl Integrator for SemiImplicitEuler {
+    /// Advancing block velocity using the current force, then block position using the updated
+    /// velocity. This scheme is symplectic, hence energy is much better conserved than with the
+    /// explicit Euler scheme for oscillatory systems.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use mersh::base::*;
+    ///
+    /// // Unit spring attached to the origin.
+    /// let spring = |b: &Block| Vec3d::new(-b.position.coords.x, 0.0, 0.0);
+    /// let energy = |b: &Block| b.get_kinetic_energy() + 0.5 * b.position.coords.x * b.position.coords.x;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_mass_density(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let (mut explicit, mut symplectic) = (block.clone(), block.clone());
+    /// let (euler, semi_euler) = (ExplicitEuler::with_force(spring), SemiImplicitEuler::with_force(spring));
+    ///
+    /// for _ in 0..1000 {
+    ///     euler.step(&mut explicit, 0.01);
+    ///     semi_euler.step(&mut symplectic, 0.01);
+    /// }
+    ///
+    /// assert!((energy(&explicit) - 0.5).abs() > 0.05);
+    /// assert!((energy(&symplectic) - 0.5).abs() < 0.005);
+    /// ```
+    fn step(&self, block: &mut Block, dt: f64)
+    {
+        if block.is_static || block.asleep { block.clear_forces(); return; }
+
+        let x = [block.position.coords.x, block.position.coords.y, block.position.coords.z];
+        let v = [block.velocity.coords.x, block.velocity.coords.y, block.velocity.coords.z];
+        let a = get_acceleration(&self.force, block, &x, &v);
+
+        block.velocity.coords.add_in(dt, &Vec3d::new(a[0], a[1], a[2]).coords);
+        block.position.coords.add_in(dt, &block.velocity.coords);
+        advance_rotation(block, dt);
+        block.clear_forces();
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of velocity Verlet integrator.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl VelocityVerlet {
+    /// Creating new integrator, without any force applied on blocks.
+    ///
+    pub fn new() -> Self
+    {
+        VelocityVerlet::default()
+    }
+
+    /// Creating new integrator, applying a force field on blocks.
+    ///
+    /// * `force` - force field applied on blocks, as a function of their state.
+    ///
+    pub fn with_force<F: Fn(&Block) -> Vec3d + 'static>(force: F) -> Self
+    {
+        VelocityVerlet{ force: Some(Box::new(force)) }
+    }
+}
+
+impl Integrator for VelocityVerlet {
+    /// Advancing block position using its current velocity and acceleration, then block velocity
+    /// using the average of the accelerations at the beginning and at the end of the time step.
+    /// The scheme is designed for forces depending on positions only, for velocity-dependent forces
+    /// the acceleration at the end of the step is evaluated using an explicit velocity prediction.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use mersh::base::*;
+    ///
+    /// let verlet = VelocityVerlet::with_force(|b: &Block| Vec3d::new(0.0, 0.0, -9.81 * b.mass));
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass_density(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_position(0.0, 0.0, 10.0)
+    ///     .set_initial_velocity(2.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// for _ in 0..100 { verlet.step(&mut block, 0.01); }
+    ///
+    /// // Analytic parabola at t = 1.
+    /// assert!((block.position.coords.x - 2.0).abs() < 1e-10);
+    /// assert!((block.position.coords.z - (10.0 - 0.5 * 9.81)).abs() < 1e-10);
+    /// assert!((block.velocity.coords.z + 9.81).abs() < 1e-10);
+    /// ```
+    fn step(&self, block: &mut Block, dt: f64)
+    {
+        if block.is_static || block.asleep { block.clear_forces(); return; }
+
+        let x = [block.position.coords.x, block.position.coords.y, block.position.coords.z];
+        let v = [block.velocity.coords.x, block.velocity.coords.y, block.velocity.coords.z];
+        let a = get_acceleration(&self.force, block, &x, &v);
+
+        let xn = axpy(&axpy(&x, dt, &v), 0.5 * dt * dt, &a);
+        let an = get_acceleration(&self.force, block, &xn, &axpy(&v, dt, &a));
+        let vn = axpy(&axpy(&v, 0.5 * dt, &a), 0.5 * dt, &an);
+
+        block.position = Pnt3d::new(xn[0], xn[1], xn[2]);
+        block.velocity = Vec3d::new(vn[0], vn[1], vn[2]);
+        advance_rotation(block, dt);
+        block.clear_forces();
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of fourth-order Runge-Kutta integrator.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl RungeKutta4 {
+    /// Creating new integrator, without any force applied on blocks.
+    ///
+    pub fn new() -> Self
+    {
+        RungeKutta4::default()
+    }
+
+    /// Creating new integrator, applying a force field on blocks.
+    ///
+    /// * `force` - force field applied on blocks, as a function of their state.
+    ///
+    pub fn with_force<F: Fn(&Block) -> Vec3d + 'static>(force: F) -> Self
+    {
+        RungeKutta4{ force: Some(Box::new(force)) }
+    }
+}
+
+impl Integrator for RungeKutta4 {
+    /// Advancing block position and velocity using the classic fourth-order Runge-Kutta scheme.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use mersh::base::*;
+    ///
+    /// // Constant force leading to a constant acceleration of (0, 0, -2).
+    /// let rk4 = RungeKutta4::with_force(|b: &Block| Vec3d::new(0.0, 0.0, -2.0 * b.mass));
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_mass_density(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_initial_velocity(1.0, 0.0, 3.0)
+    ///     .get();
+    ///
+    /// for _ in 0..10 { rk4.step(&mut block, 0.1); }
+    ///
+    /// // Analytic solution at t = 1: x = x0 + v0 t + a t^2 / 2.
+    /// assert!((block.position.coords.x - 1.0).abs() < 1e-12);
+    /// assert!((block.position.coords.z - 2.0).abs() < 1e-12);
+    /// assert!((block.velocity.coords.z - 1.0).abs() < 1e-12);
+    /// ```
+    fn step(&self, block: &mut Block, dt: f64)
+    {
+        if block.is_static || block.asleep { block.clear_forces(); return; }
+
+        let x = [block.position.coords.x, block.position.coords.y, block.position.coords.z];
+        let v = [block.velocity.coords.x, block.velocity.coords.y, block.velocity.coords.z];
+
+        let (k1x, k1v) = (v, get_acceleration(&self.force, block, &x, &v));
+        let (x2, v2) = (axpy(&x, 0.5 * dt, &k1x), axpy(&v, 0.5 * dt, &k1v));
+        let (k2x, k2v) = (v2, get_acceleration(&self.force, block, &x2, &v2));
+        let (x3, v3) = (axpy(&x, 0.5 * dt, &k2x), axpy(&v, 0.5 * dt, &k2v));
+        let (k3x, k3v) = (v3, get_acceleration(&self.force, block, &x3, &v3));
+        let (x4, v4) = (axpy(&x, dt, &k3x), axpy(&v, dt, &k3v));
+        let (k4x, k4v) = (v4, get_acceleration(&self.force, block, &x4, &v4));
+
+        let mut xn = x;
+        let mut vn = v;
+        for i in 0..3
+        {
+            xn[i] += dt / 6.0 * (k1x[i] + 2.0 * k2x[i] + 2.0 * k3x[i] + k4x[i]);
+            vn[i] += dt / 6.0 * (k1v[i] + 2.0 * k2v[i] + 2.0 * k3v[i] + k4v[i]);
+        }
+        block.position = Pnt3d::new(xn[0], xn[1], xn[2]);
+        block.velocity = Vec3d::new(vn[0], vn[1], vn[2]);
+        advance_rotation(block, dt);
+        block.clear_forces();
+    }
+}
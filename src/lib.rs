@@ -1,4 +1,48 @@
 extern crate mersh;
 
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(feature = "rand")]
+extern crate rand;
+
+/// Serialization adapters for mersh base types.
+#[cfg(feature = "serde")]
+mod serde_adapters;
+
 /// Definition of blocks.
 pub mod block;
+
+/// Definition of quaternions.
+pub mod quaternion;
+
+/// Definition of axis-aligned bounding boxes.
+pub mod aabb;
+
+/// Definition of time lines.
+pub mod timeline;
+
+/// Definition of time integrators.
+pub mod integrator;
+
+/// Definition of forces applied on blocks.
+pub mod force;
+
+/// Definition of worlds.
+pub mod world;
+
+/// Definition of collision responses between blocks.
+pub mod collision;
+
+/// Definition of trajectories.
+pub mod trajectory;
+
+/// Definition of VTK export.
+pub mod vtk;
+
+/// Definition of OBJ export.
+pub mod obj;
+
+/// Definition of random block generators.
+#[cfg(feature = "rand")]
+pub mod random;
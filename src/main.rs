@@ -1,12 +1,7 @@
 extern crate mersh;
 extern crate rody;
 
-use rody::block::*;
-
-fn forward(block: &mut Block, ts: f64)
-{
-    block.position.coords.add_in(ts, &block.velocity.coords);
-}
+use rody::integrator::*;
 
 fn main() {
 
@@ -16,6 +11,6 @@ fn main() {
         .set_initial_velocity(-1.0, 0.0, 0.0)
         .get();
 
-    forward(&mut block, 0.1);
+    ExplicitEuler::new().step(&mut block, 0.1);
     println!("{:}", block.format("_", 3));
 }
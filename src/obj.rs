@@ -0,0 +1,74 @@
+// Using blocks.
+use block::*;
+
+// Using worlds of blocks.
+use world::*;
+
+use std::io;
+
+/// Writing blocks in the Wavefront OBJ format, each block being exported as its eight corners and
+/// six quadrangular faces, ordered counter-clockwise when seen from outside of the block.
+///
+/// * `w` - writer receiving the OBJ file.
+/// * `blocks` - blocks to export.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+/// use rody::obj::*;
+///
+/// let a = BlockBuilder::new()
+///     .set_lengths(1.0, 1.0, 1.0)
+///     .get();
+///
+/// let b = BlockBuilder::new()
+///     .set_lengths(1.0, 1.0, 1.0)
+///     .set_initial_position(2.0, 0.0, 0.0)
+///     .get();
+///
+/// let mut buffer = Vec::new();
+/// write_blocks(&mut buffer, &[a, b]).unwrap();
+/// let obj = String::from_utf8(buffer).unwrap();
+///
+/// assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 16);
+/// assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), 12);
+/// assert!(obj.contains("f 9 13 15 11\n"));
+/// ```
+pub fn write_blocks<W: io::Write>(w: &mut W, blocks: &[Block]) -> io::Result<()>
+{
+    for block in blocks.iter() {
+        for corner in block.get_corners().iter() {
+            let c = &corner.coords;
+            writeln!(w, "v {} {} {}", c.x, c.y, c.z)?;
+        }
+    }
+
+    // Indexes of vertices are 1-based in the OBJ format.
+    for i in 0..blocks.len() {
+        let offset = 8 * i + 1;
+        for face in BLOCK_FACES.iter() {
+            writeln!(w, "f {} {} {} {}", face[0] + offset, face[1] + offset, face[2] + offset, face[3] + offset)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writing a single block in the Wavefront OBJ format, see `write_blocks`.
+///
+/// * `w` - writer receiving the OBJ file.
+/// * `block` - block to export.
+///
+pub fn write_block<W: io::Write>(w: &mut W, block: &Block) -> io::Result<()>
+{
+    write_blocks(w, ::std::slice::from_ref(block))
+}
+
+/// Writing all blocks of a world in the Wavefront OBJ format, see `write_blocks`.
+///
+/// * `w` - writer receiving the OBJ file.
+/// * `world` - world whose blocks are exported.
+///
+pub fn write_world<W: io::Write>(w: &mut W, world: &World) -> io::Result<()>
+{
+    write_blocks(w, &world.blocks)
+}
@@ -0,0 +1,270 @@
+// Using base tools of mersh.
+use mersh::base::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Data structure for defining quaternions, used for representing block orientations.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Quaternion {
+    /// Scalar part of the quaternion.
+    pub w: f64,
+    /// Vector part of the quaternion.
+    #[cfg_attr(feature = "serde", serde(with = "::serde_adapters::vec3d"))]
+    pub v: Vec3d,
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of quaternion services.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl Default for Quaternion {
+    /// Default quaternion is the identity rotation.
+    ///
+    fn default() -> Self
+    {
+        Quaternion::identity()
+    }
+}
+
+impl Quaternion {
+    /// Creating new quaternion from its scalar and vector components.
+    ///
+    /// * `w` - scalar part of the quaternion.
+    /// * `x` - first coordinate of the vector part of the quaternion.
+    /// * `y` - second coordinate of the vector part of the quaternion.
+    /// * `z` - third coordinate of the vector part of the quaternion.
+    ///
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self
+    {
+        Quaternion{ w, v: Vec3d::new(x, y, z) }
+    }
+
+    /// Creating identity quaternion, i.e. the quaternion associated to no rotation.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::quaternion::*;
+    ///
+    /// let q = Quaternion::identity();
+    ///
+    /// assert!((q.w - 1.0).abs() < 1e-12);
+    /// assert!(q.v.coords.norm() < 1e-12);
+    /// ```
+    pub fn identity() -> Self
+    {
+        Quaternion::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Creating unit quaternion associated to a rotation around an axis.
+    ///
+    /// * `axis` - axis of the rotation, not necessarily normalized.
+    /// * `angle` - angle of the rotation, in radians.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::quaternion::*;
+    /// use mersh::base::*;
+    ///
+    /// let q = Quaternion::from_axis_angle(&Vec3d::new(0.0, 0.0, 2.0), std::f64::consts::PI);
+    ///
+    /// assert!(q.w.abs() < 1e-12);
+    /// assert!((q.v.coords.z - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn from_axis_angle(axis: &Vec3d, angle: f64) -> Self
+    {
+        let n = axis.coords.norm();
+        if n == 0.0 { return Quaternion::identity(); }
+
+        let s = (0.5 * angle).sin() / n;
+        Quaternion::new((0.5 * angle).cos(), s * axis.coords.x, s * axis.coords.y, s * axis.coords.z)
+    }
+
+    /// Creating unit quaternion from Euler angles, using the Z-Y-X intrinsic convention: the
+    /// rotation is a rotation of `yaw` around the z axis, followed by a rotation of `pitch` around
+    /// the new y axis, followed by a rotation of `roll` around the new x axis.
+    ///
+    /// * `roll` - angle of the rotation around the x axis, in radians.
+    /// * `pitch` - angle of the rotation around the y axis, in radians.
+    /// * `yaw` - angle of the rotation around the z axis, in radians.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::quaternion::*;
+    /// use mersh::base::*;
+    ///
+    /// let q = Quaternion::from_euler(0.0, 0.0, 0.5 * std::f64::consts::PI);
+    /// let u = q.rotate(&Vec3d::new(1.0, 0.0, 0.0));
+    ///
+    /// assert!(u.coords.x.abs() < 1e-12);
+    /// assert!((u.coords.y - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn from_euler(roll: f64, pitch: f64, yaw: f64) -> Self
+    {
+        let qx = Quaternion::from_axis_angle(&Vec3d::new(1.0, 0.0, 0.0), roll);
+        let qy = Quaternion::from_axis_angle(&Vec3d::new(0.0, 1.0, 0.0), pitch);
+        let qz = Quaternion::from_axis_angle(&Vec3d::new(0.0, 0.0, 1.0), yaw);
+        qz.compose(&qy).compose(&qx)
+    }
+
+    /// Computing Hamilton product of the quaternion with another one, i.e. the rotation obtained
+    /// by applying `other` first and then the current quaternion.
+    ///
+    /// * `other` - right operand of the product.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::quaternion::*;
+    ///
+    /// let i = Quaternion::new(0.0, 1.0, 0.0, 0.0);
+    /// let j = Quaternion::new(0.0, 0.0, 1.0, 0.0);
+    /// let k = i.compose(&j);
+    ///
+    /// assert!(k.approx_eq(&Quaternion::new(0.0, 0.0, 0.0, 1.0), 1e-12));
+    /// ```
+    pub fn compose(&self, other: &Quaternion) -> Quaternion
+    {
+        let (a, b) = (&self.v.coords, &other.v.coords);
+        Quaternion::new(
+            self.w * other.w - a.x * b.x - a.y * b.y - a.z * b.z,
+            self.w * b.x + other.w * a.x + a.y * b.z - a.z * b.y,
+            self.w * b.y + other.w * a.y + a.z * b.x - a.x * b.z,
+            self.w * b.z + other.w * a.z + a.x * b.y - a.y * b.x,
+        )
+    }
+
+    /// Computing norm of the quaternion.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::quaternion::*;
+    ///
+    /// let q = Quaternion::new(1.0, 1.0, 1.0, 1.0);
+    ///
+    /// assert!((q.norm() - 2.0).abs() < 1e-12);
+    /// ```
+    pub fn norm(&self) -> f64
+    {
+        (self.w * self.w + self.v.coords.x * self.v.coords.x
+            + self.v.coords.y * self.v.coords.y + self.v.coords.z * self.v.coords.z).sqrt()
+    }
+
+    /// Normalizing quaternion in place. A zero quaternion is reset to the identity.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::quaternion::*;
+    ///
+    /// let mut q = Quaternion::new(2.0, 0.0, 0.0, 0.0);
+    /// q.normalize();
+    ///
+    /// assert!((q.w - 1.0).abs() < 1e-12);
+    /// assert!((q.norm() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn normalize(&mut self) -> &mut Self
+    {
+        let n = self.norm();
+        if n > 0.0 {
+            self.w /= n;
+            self.v.coords.x /= n;
+            self.v.coords.y /= n;
+            self.v.coords.z /= n;
+        }
+        else {
+            *self = Quaternion::identity();
+        }
+        self
+    }
+
+    /// Computing conjugate of the quaternion, i.e. the inverse rotation for unit quaternions.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::quaternion::*;
+    ///
+    /// let q = Quaternion::new(1.0, 2.0, 3.0, 4.0).conjugate();
+    ///
+    /// assert!((q.w - 1.0).abs() < 1e-12);
+    /// assert!((q.v.coords.x + 2.0).abs() < 1e-12);
+    /// assert!((q.v.coords.y + 3.0).abs() < 1e-12);
+    /// assert!((q.v.coords.z + 4.0).abs() < 1e-12);
+    /// ```
+    pub fn conjugate(&self) -> Quaternion
+    {
+        Quaternion::new(self.w, -self.v.coords.x, -self.v.coords.y, -self.v.coords.z)
+    }
+
+    /// Rotating input vector using the quaternion, assumed to be normalized.
+    ///
+    /// * `u` - vector to rotate.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::quaternion::*;
+    /// use mersh::base::*;
+    ///
+    /// // Quarter turn around z axis.
+    /// let h = (0.5 as f64).sqrt();
+    /// let q = Quaternion::new(h, 0.0, 0.0, h);
+    /// let u = q.rotate(&Vec3d::new(1.0, 0.0, 0.0));
+    ///
+    /// assert!(u.coords.x.abs() < 1e-12);
+    /// assert!((u.coords.y - 1.0).abs() < 1e-12);
+    /// assert!(u.coords.z.abs() < 1e-12);
+    /// ```
+    pub fn rotate(&self, u: &Vec3d) -> Vec3d
+    {
+        // Using u' = u + 2 w (q x u) + 2 q x (q x u), with q the vector part of the quaternion.
+        let q = &self.v.coords;
+        let c = &u.coords;
+        let t = [
+            2.0 * (q.y * c.z - q.z * c.y),
+            2.0 * (q.z * c.x - q.x * c.z),
+            2.0 * (q.x * c.y - q.y * c.x),
+        ];
+        Vec3d::new(
+            c.x + self.w * t[0] + (q.y * t[2] - q.z * t[1]),
+            c.y + self.w * t[1] + (q.z * t[0] - q.x * t[2]),
+            c.z + self.w * t[2] + (q.x * t[1] - q.y * t[0]),
+        )
+    }
+
+    /// Rotating input vector using the inverse rotation of the quaternion, assumed to be normalized.
+    ///
+    /// * `u` - vector to rotate.
+    ///
+    pub fn inverse_rotate(&self, u: &Vec3d) -> Vec3d
+    {
+        self.conjugate().rotate(u)
+    }
+
+    /// Testing if two quaternions are approximately equal, component-wise within a tolerance.
+    /// Since `q` and `-q` represent the same rotation, both are considered equal to `q`.
+    ///
+    /// * `other` - quaternion to compare with.
+    /// * `tol` - tolerance on each component.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::quaternion::*;
+    ///
+    /// let q = Quaternion::new(0.5, 0.5, 0.5, 0.5);
+    ///
+    /// assert!(q.approx_eq(&Quaternion::new(0.5, 0.5, 0.5, 0.5 + 1e-14), 1e-12));
+    /// assert!(q.approx_eq(&Quaternion::new(-0.5, -0.5, -0.5, -0.5), 1e-12));
+    /// assert!(!q.approx_eq(&Quaternion::identity(), 1e-12));
+    /// ```
+    pub fn approx_eq(&self, other: &Quaternion, tol: f64) -> bool
+    {
+        let (a, b) = (&self.v.coords, &other.v.coords);
+        let same = |s: f64| (self.w - s * other.w).abs() <= tol && (a.x - s * b.x).abs() <= tol
+            && (a.y - s * b.y).abs() <= tol && (a.z - s * b.z).abs() <= tol;
+        same(1.0) || same(-1.0)
+    }
+}
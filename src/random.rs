@@ -0,0 +1,91 @@
+// Using base tools of mersh.
+use mersh::base::*;
+
+// Using blocks.
+use block::*;
+
+// Using axis-aligned bounding boxes.
+use aabb::*;
+
+// Using random number generators.
+use rand::Rng;
+
+use std::f64::consts::PI;
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of random block generators.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+/// Generating random block of unit mass density. Lengths are sampled uniformly in `(0, max_length]`,
+/// the position is sampled uniformly so that the axis-aligned bounding box of the block lies inside
+/// the domain, and the velocity is sampled with a uniform direction and a speed sampled uniformly
+/// in `[0, max_speed]`. Blocks larger than the domain along a direction are centered in the domain
+/// along this direction.
+///
+/// * `rng` - random number generator.
+/// * `domain` - domain in which the block is generated.
+/// * `max_length` - maximal length of the block in each direction.
+/// * `max_speed` - maximal speed of the block.
+///
+/// # Examples
+/// ```
+/// extern crate mersh;
+/// # #[cfg(feature = "rand")]
+/// extern crate rand;
+/// extern crate rody;
+///
+/// # #[cfg(feature = "rand")]
+/// # fn main() {
+/// use mersh::base::*;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+/// use rody::aabb::*;
+/// use rody::random::*;
+///
+/// let mut rng = StdRng::seed_from_u64(42);
+/// let domain = Aabb::new(Pnt3d::new(-1.0, -2.0, -3.0), Pnt3d::new(1.0, 2.0, 3.0));
+///
+/// for _ in 0..100 {
+///     let block = random_block(&mut rng, &domain, 0.5, 2.0);
+///     let aabb = block.get_aabb();
+///
+///     assert!(block.lengths.iter().all(|l| *l > 0.0 && *l <= 0.5));
+///     assert!(aabb.min.coords.x >= -1.0 && aabb.max.coords.x <= 1.0);
+///     assert!(aabb.min.coords.y >= -2.0 && aabb.max.coords.y <= 2.0);
+///     assert!(aabb.min.coords.z >= -3.0 && aabb.max.coords.z <= 3.0);
+///     assert!(block.velocity.coords.norm() <= 2.0 + 1e-12);
+/// }
+/// # }
+/// # #[cfg(not(feature = "rand"))]
+/// # fn main() {}
+/// ```
+pub fn random_block<R: Rng + ?Sized>(rng: &mut R, domain: &Aabb, max_length: f64, max_speed: f64) -> Block
+{
+    let min = [domain.min.coords.x, domain.min.coords.y, domain.min.coords.z];
+    let max = [domain.max.coords.x, domain.max.coords.y, domain.max.coords.z];
+
+    let mut lengths = [0.0; 3];
+    let mut p = [0.0; 3];
+    for i in 0..3
+    {
+        lengths[i] = max_length * (1.0 - rng.gen::<f64>());
+        let (lo, hi) = (min[i] + 0.5 * lengths[i], max[i] - 0.5 * lengths[i]);
+        p[i] = if lo < hi { lo + (hi - lo) * rng.gen::<f64>() } else { 0.5 * (min[i] + max[i]) };
+    }
+
+    // Sampling direction uniformly on the unit sphere.
+    let z = 2.0 * rng.gen::<f64>() - 1.0;
+    let phi = 2.0 * PI * rng.gen::<f64>();
+    let r = (1.0 - z * z).sqrt();
+    let speed = max_speed * rng.gen::<f64>();
+
+    let mut block = BlockBuilder::new()
+        .set_mass_density(1.0)
+        .set_lengths(lengths[0], lengths[1], lengths[2])
+        .set_initial_position(p[0], p[1], p[2])
+        .get();
+    block.velocity = Vec3d::new(speed * r * phi.cos(), speed * r * phi.sin(), speed * z);
+    block
+}
@@ -0,0 +1,45 @@
+// Using base tools of mersh.
+use mersh::base::*;
+
+// Using serde traits.
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializing and deserializing points as arrays of coordinates.
+pub mod pnt3d {
+    use super::*;
+
+    /// Serializing point as an array of coordinates.
+    ///
+    pub fn serialize<S: Serializer>(p: &Pnt3d, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        [p.coords.x, p.coords.y, p.coords.z].serialize(serializer)
+    }
+
+    /// Deserializing point from an array of coordinates.
+    ///
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pnt3d, D::Error>
+    {
+        let c = <[f64; 3]>::deserialize(deserializer)?;
+        Ok(Pnt3d::new(c[0], c[1], c[2]))
+    }
+}
+
+/// Serializing and deserializing vectors as arrays of coordinates.
+pub mod vec3d {
+    use super::*;
+
+    /// Serializing vector as an array of coordinates.
+    ///
+    pub fn serialize<S: Serializer>(v: &Vec3d, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        [v.coords.x, v.coords.y, v.coords.z].serialize(serializer)
+    }
+
+    /// Deserializing vector from an array of coordinates.
+    ///
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec3d, D::Error>
+    {
+        let c = <[f64; 3]>::deserialize(deserializer)?;
+        Ok(Vec3d::new(c[0], c[1], c[2]))
+    }
+}
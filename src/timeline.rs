@@ -0,0 +1,761 @@
+// Using worlds of blocks.
+use world::*;
+
+/// Trait for defining time lines, i.e. schedules of times used for driving simulations.
+///
+/// # Examples
+/// ```
+/// use rody::timeline::*;
+///
+/// fn duration(timeline: &mut dyn TimeLine) -> f64 {
+///     let mut duration = 0.0;
+///     loop {
+///         let dt = timeline.time_step();
+///         if timeline.advance().is_none() { break; }
+///         duration += dt;
+///     }
+///     duration
+/// }
+///
+/// assert!((duration(&mut RegularTimeLine::new(0.0, 1.0, 10)) - 1.0).abs() < 1e-12);
+/// assert!((duration(&mut IrregularTimeLine::new(vec![0.0, 0.1, 0.3, 0.6])) - 0.6).abs() < 1e-12);
+/// ```
+pub trait TimeLine {
+    /// Accessing current time of the time line, i.e. next time yielded when advancing.
+    fn current_time(&self) -> f64;
+
+    /// Accessing time step from the current time to the next one, i.e. the next yielded time, or
+    /// the excluded maximal time for the last time of half-open time lines. The time step is zero
+    /// at the last time of closed time lines, and once the time line is exhausted.
+    fn time_step(&self) -> f64;
+
+    /// Yielding current time and advancing to the next one, nothing is returned once the time line
+    /// is exhausted.
+    fn advance(&mut self) -> Option<f64>;
+}
+
+/// Data structure for defining regular time lines, i.e. iterators over regularly spaced times of
+/// a half-open interval [min, max), or of a closed interval [min, max] for inclusive time lines.
+/// Times are computed from their step index, so that no rounding error accumulates along the time
+/// line.
+#[derive(Clone, Debug)]
+pub struct RegularTimeLine {
+    /// First time of the time line.
+    min_time: f64,
+    /// Number of steps of the time line.
+    nstep: usize,
+    /// Number of times of the time line, i.e. the number of steps plus one for inclusive time lines.
+    ntime: usize,
+    /// Time step between two successive times.
+    time_step: f64,
+    /// Maximal time of the time line, which is only reached for inclusive time lines.
+    max_time: f64,
+    /// Step index of the next time yielded when iterating forward.
+    step: usize,
+    /// Step index following the next time yielded when iterating backward.
+    end_step: usize,
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of regular time line.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl RegularTimeLine {
+    /// Creating new regular time line. If `min >= max` or if the number of steps is zero, the
+    /// time step is zero and the time line yields no time.
+    ///
+    /// * `min` - first time of the time line.
+    /// * `max` - maximal time of the time line, which is never reached.
+    /// * `nstep` - number of steps between `min` and `max`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let times: Vec<f64> = RegularTimeLine::new(0.0, 1.0, 10).collect();
+    ///
+    /// assert_eq!(times.len(), 10);
+    /// assert!(times[0].abs() < 1e-12);
+    /// assert!((times[9] - 0.9).abs() < 1e-12);
+    ///
+    /// assert_eq!(RegularTimeLine::new(1.0, 0.0, 10).count(), 0);
+    ///
+    /// // No drift over many steps.
+    /// let last = RegularTimeLine::new(0.0, 1.0, 1_000_000).last().unwrap();
+    /// assert!((last - (1.0 - 1e-6)).abs() < 1e-9);
+    /// ```
+    pub fn new(min: f64, max: f64, nstep: usize) -> Self
+    {
+        let nstep = if min >= max { 0 } else { nstep };
+        let (time_step, max_time) = if nstep == 0 { (0.0, min) } else { ((max - min) / nstep as f64, max) };
+        RegularTimeLine{ min_time: min, nstep, ntime: nstep, time_step, max_time, step: 0, end_step: nstep }
+    }
+
+    /// Creating new inclusive regular time line, yielding `nstep + 1` times from `min` to `max`
+    /// exactly. If `min >= max` or if the number of steps is zero, the time line yields no time.
+    ///
+    /// * `min` - first time of the time line.
+    /// * `max` - last time of the time line.
+    /// * `nstep` - number of steps between `min` and `max`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let times: Vec<f64> = RegularTimeLine::new_inclusive(0.0, 1.0, 10).collect();
+    ///
+    /// assert_eq!(times.len(), 11);
+    /// assert!(times[0].abs() < 1e-12);
+    /// assert_eq!(times[10], 1.0);
+    /// ```
+    pub fn new_inclusive(min: f64, max: f64, nstep: usize) -> Self
+    {
+        let mut timeline = RegularTimeLine::new(min, max, nstep);
+        if timeline.nstep > 0 {
+            timeline.ntime = timeline.nstep + 1;
+            timeline.end_step = timeline.ntime;
+        }
+        timeline
+    }
+
+    /// Resetting time line to its first time, so that it can be iterated again.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let mut timeline = RegularTimeLine::new(0.0, 1.0, 10);
+    /// let first: Vec<f64> = timeline.by_ref().collect();
+    ///
+    /// timeline.reset();
+    /// let second: Vec<f64> = timeline.collect();
+    ///
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn reset(&mut self) -> &mut Self
+    {
+        self.step = 0;
+        self.end_step = self.ntime;
+        self
+    }
+
+    /// Accessing next time yielded when iterating forward, without advancing the time line.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let mut timeline = RegularTimeLine::new(0.0, 1.0, 2);
+    ///
+    /// assert_eq!(timeline.peek(), Some(0.0));
+    /// assert_eq!(timeline.next(), Some(0.0));
+    /// assert_eq!(timeline.peek(), Some(0.5));
+    /// assert_eq!(timeline.next(), Some(0.5));
+    /// assert_eq!(timeline.peek(), None);
+    /// ```
+    pub fn peek(&self) -> Option<f64>
+    {
+        if self.step < self.end_step { Some(self.get_time(self.step)) } else { None }
+    }
+
+    /// Accessing current time of the time line, i.e. next time yielded when iterating forward.
+    ///
+    pub fn current_time(&self) -> f64
+    {
+        self.get_time(self.step)
+    }
+
+    /// Computing number of times remaining to be yielded, without consuming the time line.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let mut timeline = RegularTimeLine::new(0.0, 1.0, 3);
+    ///
+    /// assert_eq!(timeline.remaining(), 3);
+    /// timeline.next();
+    /// assert_eq!(timeline.remaining(), 2);
+    /// timeline.next();
+    /// timeline.next();
+    /// assert_eq!(timeline.remaining(), 0);
+    /// assert!(timeline.is_done());
+    ///
+    /// assert_eq!(RegularTimeLine::new(0.0, 0.0, 3).remaining(), 0);
+    /// ```
+    pub fn remaining(&self) -> usize
+    {
+        self.end_step - self.step
+    }
+
+    /// Testing if all times of the time line have been yielded.
+    ///
+    pub fn is_done(&self) -> bool
+    {
+        self.remaining() == 0
+    }
+
+    /// Accessing current step index of the time line, i.e. the index of the next time yielded when
+    /// iterating forward, starting at 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let mut timeline = RegularTimeLine::new(0.0, 1.0, 5);
+    ///
+    /// for i in 0..5 {
+    ///     assert_eq!(timeline.step(), i);
+    ///     timeline.next();
+    /// }
+    /// assert_eq!(timeline.step(), 5);
+    ///
+    /// timeline.reset();
+    /// assert_eq!(timeline.step(), 0);
+    /// ```
+    pub fn step(&self) -> usize
+    {
+        self.step
+    }
+
+    /// Accessing time step from the current time to the next one. The time step of the time line
+    /// is returned, except at the last time of inclusive time lines and once the time line is
+    /// exhausted, where it is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let mut timeline = RegularTimeLine::new(0.0, 1.0, 2);
+    /// assert_eq!(timeline.time_step(), 0.5);
+    /// timeline.next();
+    /// assert_eq!(timeline.time_step(), 0.5);
+    /// timeline.next();
+    /// assert_eq!(timeline.time_step(), 0.0);
+    ///
+    /// let mut timeline = RegularTimeLine::new_inclusive(0.0, 1.0, 2);
+    /// timeline.next();
+    /// timeline.next();
+    /// assert_eq!(timeline.current_time(), 1.0);
+    /// assert_eq!(timeline.time_step(), 0.0);
+    /// ```
+    pub fn time_step(&self) -> f64
+    {
+        if self.step < self.nstep && self.step < self.end_step { self.time_step } else { 0.0 }
+    }
+
+    /// Accessing maximal time of the time line, which is only reached for inclusive time lines.
+    ///
+    pub fn max_time(&self) -> f64
+    {
+        self.max_time
+    }
+
+    /// Consuming time line into an iterator over pairs of times and time steps to the next times.
+    /// The time step is the one of the time line, except for the last time of inclusive time lines
+    /// which is associated to a zero time step.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let steps: Vec<(f64, f64)> = RegularTimeLine::new(0.0, 1.0, 4).with_steps().collect();
+    ///
+    /// assert_eq!(steps.len(), 4);
+    /// for (i, (t, dt)) in steps.iter().enumerate() {
+    ///     assert!((t - 0.25 * i as f64).abs() < 1e-12);
+    ///     assert_eq!(*dt, 0.25);
+    /// }
+    ///
+    /// let last = RegularTimeLine::new_inclusive(0.0, 1.0, 4).with_steps().last();
+    /// assert_eq!(last, Some((1.0, 0.0)));
+    /// ```
+    pub fn with_steps(self) -> impl Iterator<Item = (f64, f64)>
+    {
+        let (nstep, time_step) = (self.nstep, self.time_step);
+        (self.step..).zip(self).map(move |(step, time)| (time, if step < nstep { time_step } else { 0.0 }))
+    }
+
+    /// Computing time associated to a step index, the last step being mapped to the maximal time.
+    ///
+    fn get_time(&self, step: usize) -> f64
+    {
+        if step == self.nstep { self.max_time } else { self.min_time + step as f64 * self.time_step }
+    }
+}
+
+impl Iterator for RegularTimeLine {
+    type Item = f64;
+
+    /// Yielding current time and advancing to the next one.
+    ///
+    fn next(&mut self) -> Option<f64>
+    {
+        if self.step >= self.end_step { return None; }
+
+        self.step += 1;
+        Some(self.get_time(self.step - 1))
+    }
+
+    /// Computing bounds on the number of remaining times, which is exactly known.
+    ///
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl DoubleEndedIterator for RegularTimeLine {
+    /// Yielding last remaining time of the time line, times are yielded in descending order down
+    /// to the current time.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let times: Vec<f64> = RegularTimeLine::new(0.0, 1.0, 4).rev().collect();
+    ///
+    /// assert_eq!(times, vec![0.75, 0.5, 0.25, 0.0]);
+    ///
+    /// let mut timeline = RegularTimeLine::new(0.0, 1.0, 3);
+    ///
+    /// assert!(timeline.next_back().is_some());
+    /// assert!(timeline.next().is_some());
+    /// assert!(timeline.next_back().is_some());
+    /// assert!(timeline.next().is_none());
+    /// assert!(timeline.next_back().is_none());
+    /// ```
+    fn next_back(&mut self) -> Option<f64>
+    {
+        if self.step >= self.end_step { return None; }
+
+        self.end_step -= 1;
+        Some(self.get_time(self.end_step))
+    }
+}
+
+impl ExactSizeIterator for RegularTimeLine {
+    /// Computing number of remaining times of the time line.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let mut timeline = RegularTimeLine::new(0.0, 1.0, 10);
+    /// assert_eq!(timeline.len(), 10);
+    ///
+    /// timeline.next();
+    /// timeline.next_back();
+    /// assert_eq!(timeline.len(), 8);
+    ///
+    /// assert_eq!(RegularTimeLine::new(1.0, 1.0, 10).len(), 0);
+    /// ```
+    fn len(&self) -> usize
+    {
+        self.remaining()
+    }
+}
+
+/// Data structure for defining irregular time lines, i.e. iterators over an explicit sequence of
+/// times, assumed to be sorted in ascending order.
+#[derive(Clone, Debug, Default)]
+pub struct IrregularTimeLine {
+    /// Times of the time line.
+    times: Vec<f64>,
+    /// Index of the next time yielded when iterating.
+    index: usize,
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of irregular time line.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl IrregularTimeLine {
+    /// Creating new irregular time line.
+    ///
+    /// * `times` - times of the time line, sorted in ascending order.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let mut timeline = IrregularTimeLine::new(vec![0.0, 0.1, 0.3, 0.6]);
+    /// let mut times = Vec::new();
+    /// let mut steps = Vec::new();
+    ///
+    /// loop {
+    ///     let dt = timeline.time_step();
+    ///     match timeline.next() {
+    ///         Some(t) => { times.push(t); steps.push(dt); },
+    ///         None => break,
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(times, vec![0.0, 0.1, 0.3, 0.6]);
+    /// assert!((steps[0] - 0.1).abs() < 1e-12);
+    /// assert!((steps[1] - 0.2).abs() < 1e-12);
+    /// assert!((steps[2] - 0.3).abs() < 1e-12);
+    /// assert!(steps[3].abs() < 1e-12);
+    /// ```
+    pub fn new(times: Vec<f64>) -> Self
+    {
+        IrregularTimeLine{ times, index: 0 }
+    }
+
+    /// Accessing current time of the time line, i.e. next time yielded when iterating. The last
+    /// time is returned once the time line is exhausted, and zero if it is empty.
+    ///
+    pub fn current_time(&self) -> f64
+    {
+        match self.times.get(self.index) {
+            Some(t) => *t,
+            None => self.times.last().cloned().unwrap_or(0.0),
+        }
+    }
+
+    /// Accessing gap between the current time and the following one, zero at the last time.
+    ///
+    pub fn time_step(&self) -> f64
+    {
+        match (self.times.get(self.index), self.times.get(self.index + 1)) {
+            (Some(t0), Some(t1)) => t1 - t0,
+            _ => 0.0,
+        }
+    }
+}
+
+impl Iterator for IrregularTimeLine {
+    type Item = f64;
+
+    /// Yielding current time and advancing to the next one.
+    ///
+    fn next(&mut self) -> Option<f64>
+    {
+        let time = self.times.get(self.index).cloned();
+        if time.is_some() { self.index += 1; }
+        time
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of time line trait.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl TimeLine for RegularTimeLine {
+    fn current_time(&self) -> f64
+    {
+        RegularTimeLine::current_time(self)
+    }
+
+    fn time_step(&self) -> f64
+    {
+        RegularTimeLine::time_step(self)
+    }
+
+    fn advance(&mut self) -> Option<f64>
+    {
+        self.next()
+    }
+}
+
+impl TimeLine for IrregularTimeLine {
+    fn current_time(&self) -> f64
+    {
+        IrregularTimeLine::current_time(self)
+    }
+
+    fn time_step(&self) -> f64
+    {
+        IrregularTimeLine::time_step(self)
+    }
+
+    fn advance(&mut self) -> Option<f64>
+    {
+        self.next()
+    }
+}
+
+/// Data structure for defining geometric time lines, i.e. iterators over times whose successive
+/// gaps grow (or shrink) by a constant ratio.
+#[derive(Clone, Debug)]
+pub struct GeometricTimeLine {
+    /// First time of the time line.
+    min_time: f64,
+    /// Ratio between two successive time steps.
+    ratio: f64,
+    /// First time step of the time line.
+    initial_time_step: f64,
+    /// Number of times of the time line.
+    nstep: usize,
+    /// Step index of the next time yielded when iterating.
+    step: usize,
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of geometric time line.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl GeometricTimeLine {
+    /// Creating new geometric time line, yielding `nstep` times starting from `min`, the gap
+    /// between times `k` and `k + 1` being `initial_dt * ratio^k`. A non-positive ratio or time
+    /// step yields no time.
+    ///
+    /// * `min` - first time of the time line.
+    /// * `ratio` - ratio between two successive time steps.
+    /// * `initial_dt` - first time step of the time line.
+    /// * `nstep` - number of times of the time line.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let times: Vec<f64> = GeometricTimeLine::new(0.0, 1.1, 0.1, 5).collect();
+    ///
+    /// assert_eq!(times.len(), 5);
+    /// assert!(times[0].abs() < 1e-12);
+    /// for i in 1..4 {
+    ///     let ratio = (times[i + 1] - times[i]) / (times[i] - times[i - 1]);
+    ///     assert!((ratio - 1.1).abs() < 1e-12);
+    /// }
+    ///
+    /// assert_eq!(GeometricTimeLine::new(0.0, -1.0, 0.1, 5).count(), 0);
+    /// ```
+    pub fn new(min: f64, ratio: f64, initial_dt: f64, nstep: usize) -> Self
+    {
+        let nstep = if ratio > 0.0 && initial_dt > 0.0 { nstep } else { 0 };
+        GeometricTimeLine{ min_time: min, ratio, initial_time_step: initial_dt, nstep, step: 0 }
+    }
+
+    /// Accessing current time of the time line, i.e. next time yielded when iterating.
+    ///
+    pub fn current_time(&self) -> f64
+    {
+        self.get_time(self.step)
+    }
+
+    /// Accessing gap between the current time and the following one, zero once the time line is
+    /// exhausted.
+    ///
+    pub fn time_step(&self) -> f64
+    {
+        if self.step < self.nstep { self.initial_time_step * self.ratio.powi(self.step as i32) } else { 0.0 }
+    }
+
+    /// Computing time associated to a step index, using the closed form of the geometric sum.
+    ///
+    fn get_time(&self, step: usize) -> f64
+    {
+        if (self.ratio - 1.0).abs() < 1e-12 {
+            self.min_time + step as f64 * self.initial_time_step
+        }
+        else {
+            self.min_time + self.initial_time_step * (self.ratio.powi(step as i32) - 1.0) / (self.ratio - 1.0)
+        }
+    }
+}
+
+impl Iterator for GeometricTimeLine {
+    type Item = f64;
+
+    /// Yielding current time and advancing to the next one.
+    ///
+    fn next(&mut self) -> Option<f64>
+    {
+        if self.step >= self.nstep { return None; }
+
+        self.step += 1;
+        Some(self.get_time(self.step - 1))
+    }
+}
+
+impl TimeLine for GeometricTimeLine {
+    fn current_time(&self) -> f64
+    {
+        GeometricTimeLine::current_time(self)
+    }
+
+    fn time_step(&self) -> f64
+    {
+        GeometricTimeLine::time_step(self)
+    }
+
+    fn advance(&mut self) -> Option<f64>
+    {
+        self.next()
+    }
+}
+
+/// Data structure for defining time lines constrained by a CFL condition, i.e. iterators over times
+/// of a half-open interval [min, max) whose time steps are such that the fastest block moves at
+/// most a fraction of a reference length per step. The maximal speed of blocks is updated on
+/// demand, the time step being recomputed accordingly.
+#[derive(Clone, Debug)]
+pub struct CflTimeLine {
+    /// Next time yielded when iterating.
+    time: f64,
+    /// Maximal time of the time line, which is never reached.
+    max_time: f64,
+    /// CFL number, i.e. the fraction of the reference length travelled per step.
+    cfl: f64,
+    /// Reference length, e.g. the size of a cell.
+    length: f64,
+    /// Maximal speed of blocks.
+    max_speed: f64,
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of CFL time line.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl CflTimeLine {
+    /// Creating new CFL time line, with a zero maximal speed of blocks. As long as the maximal
+    /// speed is zero, the time step spans the remaining interval.
+    ///
+    /// * `min` - first time of the time line.
+    /// * `max` - maximal time of the time line, which is never reached.
+    /// * `cfl` - CFL number, i.e. the fraction of the reference length travelled per step.
+    /// * `length` - reference length, e.g. the size of a cell.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let mut timeline = CflTimeLine::new(0.0, 1.0, 0.5, 0.1);
+    /// timeline.set_max_speed(2.0);
+    ///
+    /// assert!((timeline.time_step() - 0.025).abs() < 1e-12);
+    /// assert_eq!(timeline.next(), Some(0.0));
+    /// assert!((timeline.current_time() - 0.025).abs() < 1e-12);
+    ///
+    /// // Recomputing time step as blocks slow down.
+    /// timeline.set_max_speed(0.5);
+    /// assert!((timeline.time_step() - 0.1).abs() < 1e-12);
+    ///
+    /// // Last time step is clamped to the end of the time line.
+    /// let times: Vec<f64> = timeline.collect();
+    /// assert_eq!(times.len(), 10);
+    /// assert!((times[9] - 0.925).abs() < 1e-12);
+    /// ```
+    pub fn new(min: f64, max: f64, cfl: f64, length: f64) -> Self
+    {
+        CflTimeLine{ time: min, max_time: max, cfl, length, max_speed: 0.0 }
+    }
+
+    /// Setting maximal speed of blocks, used for computing the time step.
+    ///
+    /// * `max_speed` - maximal speed of blocks.
+    ///
+    pub fn set_max_speed(&mut self, max_speed: f64) -> &mut Self
+    {
+        self.max_speed = max_speed.abs();
+        self
+    }
+
+    /// Updating maximal speed of blocks from the blocks of a world.
+    ///
+    /// * `world` - world whose blocks are considered.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::timeline::*;
+    /// use rody::world::*;
+    ///
+    /// let mut world = World::new();
+    /// world.push(BlockBuilder::new().set_initial_velocity(1.0, 0.0, 0.0).get())
+    ///     .push(BlockBuilder::new().set_initial_velocity(0.0, 3.0, 4.0).get());
+    ///
+    /// let mut timeline = CflTimeLine::new(0.0, 1.0, 0.5, 0.1);
+    /// timeline.update_from_world(&world);
+    ///
+    /// assert!((timeline.time_step() - 0.01).abs() < 1e-12);
+    /// ```
+    pub fn update_from_world(&mut self, world: &World) -> &mut Self
+    {
+        let max_speed = world.iter().map(|b| b.speed()).fold(0.0, f64::max);
+        self.set_max_speed(max_speed)
+    }
+
+    /// Accessing current time of the time line, i.e. next time yielded when iterating.
+    ///
+    pub fn current_time(&self) -> f64
+    {
+        self.time
+    }
+
+    /// Computing time step from the current time, i.e. the CFL number times the reference length
+    /// divided by the maximal speed of blocks, clamped to the remaining interval. The time step
+    /// spans the remaining interval if the maximal speed is zero. If no time step satisfying the
+    /// CFL condition advances the time, e.g. for an infinite maximal speed or a zero reference
+    /// length, the time step is zero and the time line stops yielding times.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::timeline::*;
+    ///
+    /// let mut timeline = CflTimeLine::new(0.0, 1.0, 0.5, 0.1);
+    /// timeline.set_max_speed(std::f64::INFINITY);
+    ///
+    /// assert_eq!(timeline.time_step(), 0.0);
+    /// assert_eq!(timeline.take(5).count(), 0);
+    ///
+    /// let mut timeline = CflTimeLine::new(1e20, 2e20, 0.5, 0.1);
+    /// timeline.set_max_speed(1.0);
+    /// assert_eq!(timeline.take(5).count(), 0);
+    ///
+    /// let mut timeline = CflTimeLine::new(0.0, 1.0, 0.5, 0.0);
+    /// timeline.set_max_speed(1.0);
+    /// assert_eq!(timeline.time_step(), 0.0);
+    /// ```
+    pub fn time_step(&self) -> f64
+    {
+        let remaining = (self.max_time - self.time).max(0.0);
+        if self.max_speed == 0.0 { return remaining; }
+
+        let dt = self.cfl * self.length / self.max_speed;
+        if dt.is_finite() && self.time + dt > self.time { remaining.min(dt) } else { 0.0 }
+    }
+}
+
+impl Iterator for CflTimeLine {
+    type Item = f64;
+
+    /// Yielding current time and advancing to the next one. Nothing is yielded once the end of the
+    /// time line is reached, or while the time step is zero, see `time_step`.
+    ///
+    fn next(&mut self) -> Option<f64>
+    {
+        let (time, dt) = (self.time, self.time_step());
+        if time >= self.max_time || dt <= 0.0 { return None; }
+
+        self.time = if dt >= self.max_time - time { self.max_time } else { time + dt };
+        Some(time)
+    }
+}
+
+impl TimeLine for CflTimeLine {
+    fn current_time(&self) -> f64
+    {
+        CflTimeLine::current_time(self)
+    }
+
+    fn time_step(&self) -> f64
+    {
+        CflTimeLine::time_step(self)
+    }
+
+    fn advance(&mut self) -> Option<f64>
+    {
+        self.next()
+    }
+}
@@ -0,0 +1,272 @@
+// Using base tools of mersh.
+use mersh::base::*;
+
+// Using blocks.
+use block::*;
+
+use std::io;
+use std::slice;
+
+/// Data structure for defining samples of a trajectory, i.e. the kinematic state of a block at a
+/// given time.
+#[derive(Clone, Default, Debug)]
+pub struct Sample {
+    /// Time of the sample.
+    pub time: f64,
+    /// Position of the block center of mass.
+    pub position: Pnt3d,
+    /// Velocity of the block center of mass.
+    pub velocity: Vec3d,
+}
+
+/// Data structure for recording trajectories of blocks. Only positions and velocities of blocks
+/// are recorded.
+#[derive(Clone, Default, Debug)]
+pub struct Trajectory {
+    /// Recorded samples, in recording order.
+    samples: Vec<Sample>,
+}
+
+/// Helper class for writing trajectories of blocks on the fly in an XYZ-style format, i.e. one line
+/// `px py pz` per recorded position of the block center of mass.
+pub struct TrajectoryWriter<W: io::Write> {
+    /// Output stream.
+    writer: W,
+    /// Number of decimal for formatting values.
+    decimal: usize,
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of trajectory services.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl Trajectory {
+    /// Creating new empty trajectory.
+    ///
+    pub fn new() -> Self
+    {
+        Trajectory::default()
+    }
+
+    /// Recording state of a block at a given time.
+    ///
+    /// * `time` - time of the record.
+    /// * `block` - block whose state is recorded.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use rody::trajectory::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let mut trajectory = Trajectory::new();
+    /// let integrator = ExplicitEuler::new();
+    /// for i in 0..3 {
+    ///     trajectory.record(0.5 * i as f64, &block);
+    ///     integrator.step(&mut block, 0.5);
+    /// }
+    ///
+    /// assert_eq!(trajectory.len(), 3);
+    /// for (i, sample) in trajectory.iter().enumerate() {
+    ///     assert!((sample.time - 0.5 * i as f64).abs() < 1e-12);
+    ///     assert!((sample.position.coords.x - 0.5 * i as f64).abs() < 1e-12);
+    ///     assert!((sample.velocity.coords.x - 1.0).abs() < 1e-12);
+    /// }
+    /// ```
+    pub fn record(&mut self, time: f64, block: &Block) -> &mut Self
+    {
+        self.samples.push(Sample{ time, position: block.position.clone(), velocity: block.velocity.clone() });
+        self
+    }
+
+    /// Accessing number of recorded samples.
+    ///
+    pub fn len(&self) -> usize
+    {
+        self.samples.len()
+    }
+
+    /// Testing if no sample has been recorded.
+    ///
+    pub fn is_empty(&self) -> bool
+    {
+        self.samples.is_empty()
+    }
+
+    /// Iterating over recorded samples, in recording order.
+    ///
+    pub fn iter(&self) -> slice::Iter<'_, Sample>
+    {
+        self.samples.iter()
+    }
+
+    /// Interpolating state of the recorded block at a given time, by linearly interpolating
+    /// position and velocity between the two recorded samples bracketing the time, see
+    /// `Block::lerp`. Samples are assumed to be recorded in ascending order of time. Only the
+    /// position and the velocity of the returned block are set, see `Block::from_state`. Nothing
+    /// is returned for times outside of the recorded range.
+    ///
+    /// * `t` - time at which the state is interpolated.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::trajectory::*;
+    ///
+    /// let mut trajectory = Trajectory::new();
+    /// trajectory.record(0.0, &Block::from_state(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0]))
+    ///     .record(2.0, &Block::from_state(&[2.0, 4.0, 0.0, 3.0, 0.0, 0.0]));
+    ///
+    /// let block = trajectory.interpolate(1.0).unwrap();
+    ///
+    /// assert!((block.position.coords.x - 1.0).abs() < 1e-12);
+    /// assert!((block.position.coords.y - 2.0).abs() < 1e-12);
+    /// assert!((block.velocity.coords.x - 2.0).abs() < 1e-12);
+    /// assert!(trajectory.interpolate(2.0).is_some());
+    /// assert!(trajectory.interpolate(-0.1).is_none());
+    /// assert!(trajectory.interpolate(2.1).is_none());
+    /// ```
+    pub fn interpolate(&self, t: f64) -> Option<Block>
+    {
+        let state = |s: &Sample| {
+            let (p, v) = (&s.position.coords, &s.velocity.coords);
+            [p.x, p.y, p.z, v.x, v.y, v.z]
+        };
+
+        if let Some(s) = self.samples.iter().find(|s| s.time == t) { return Some(Block::from_state(&state(s))); }
+        self.samples.windows(2).find(|w| w[0].time < t && t < w[1].time).map(|w| {
+            let alpha = (t - w[0].time) / (w[1].time - w[0].time);
+            Block::lerp(&Block::from_state(&state(&w[0])), &Block::from_state(&state(&w[1])), alpha)
+        })
+    }
+
+    /// Computing total distance travelled along the trajectory, i.e. the sum of the distances
+    /// between successive recorded positions. Trajectories with less than two samples are
+    /// associated to a zero distance.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use rody::timeline::*;
+    /// use rody::trajectory::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_initial_velocity(3.0, 0.0, 4.0)
+    ///     .get();
+    ///
+    /// let trajectory = run(&mut block, RegularTimeLine::new(0.0, 2.0, 20), &ExplicitEuler::new());
+    ///
+    /// assert!((trajectory.total_distance() - 5.0 * 2.0).abs() < 1e-12);
+    /// assert!((trajectory.max_speed() - 5.0).abs() < 1e-12);
+    /// assert!(Trajectory::new().total_distance().abs() < 1e-12);
+    /// ```
+    pub fn total_distance(&self) -> f64
+    {
+        self.samples.windows(2).map(|w| {
+            let (p, q) = (&w[0].position.coords, &w[1].position.coords);
+            ((q.x - p.x).powi(2) + (q.y - p.y).powi(2) + (q.z - p.z).powi(2)).sqrt()
+        }).sum()
+    }
+
+    /// Computing maximal recorded speed along the trajectory, zero for empty trajectories.
+    ///
+    pub fn max_speed(&self) -> f64
+    {
+        self.samples.iter().map(|s| s.velocity.coords.norm()).fold(0.0, f64::max)
+    }
+
+    /// Writing recorded samples as comma-separated values, with a header line `t,px,py,pz,vx,vy,vz`
+    /// followed by one line per sample.
+    ///
+    /// * `w` - output stream.
+    /// * `decimal` - number of decimal for formatting values.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::trajectory::*;
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let mut buffer: Vec<u8> = Vec::new();
+    /// Trajectory::new().record(0.0, &block).write_csv(&mut buffer, 1).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(buffer).unwrap(), "t,px,py,pz,vx,vy,vz\n0.0,0.0,0.0,0.0,1.0,0.0,0.0\n");
+    /// ```
+    pub fn write_csv<W: io::Write>(&self, w: &mut W, decimal: usize) -> io::Result<()>
+    {
+        writeln!(w, "t,px,py,pz,vx,vy,vz")?;
+        for s in self.samples.iter()
+        {
+            let (p, v) = (&s.position.coords, &s.velocity.coords);
+            writeln!(w, "{:.*},{:.*},{:.*},{:.*},{:.*},{:.*},{:.*}",
+                decimal, s.time, decimal, p.x, decimal, p.y, decimal, p.z, decimal, v.x, decimal, v.y, decimal, v.z)?;
+        }
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of trajectory writer.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl<W: io::Write> TrajectoryWriter<W> {
+    /// Creating new trajectory writer.
+    ///
+    /// * `writer` - output stream.
+    /// * `decimal` - number of decimal for formatting values.
+    ///
+    pub fn new(writer: W, decimal: usize) -> Self
+    {
+        TrajectoryWriter{ writer, decimal }
+    }
+
+    /// Writing position of the center of mass of a block, as a single line `px py pz`.
+    ///
+    /// * `block` - block whose position is written.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use rody::trajectory::*;
+    ///
+    /// let mut block = BlockBuilder::new()
+    ///     .set_initial_velocity(1.0, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let mut writer = TrajectoryWriter::new(Vec::new(), 1);
+    /// let integrator = ExplicitEuler::new();
+    /// for _ in 0..3 {
+    ///     writer.record(&block).unwrap();
+    ///     integrator.step(&mut block, 0.5);
+    /// }
+    ///
+    /// let xyz = String::from_utf8(writer.into_inner()).unwrap();
+    /// assert_eq!(xyz.lines().count(), 3);
+    /// assert_eq!(xyz, "0.0 0.0 0.0\n0.5 0.0 0.0\n1.0 0.0 0.0\n");
+    /// ```
+    pub fn record(&mut self, block: &Block) -> io::Result<()>
+    {
+        let (d, p) = (self.decimal, &block.position.coords);
+        writeln!(self.writer, "{:.*} {:.*} {:.*}", d, p.x, d, p.y, d, p.z)
+    }
+
+    /// Consuming trajectory writer, returning its output stream.
+    ///
+    pub fn into_inner(self) -> W
+    {
+        self.writer
+    }
+}
@@ -0,0 +1,96 @@
+// Using blocks.
+use block::*;
+
+// Using worlds of blocks.
+use world::*;
+
+use std::io;
+
+/// Ordering of block corners following the VTK hexahedron convention, block corners being indexed
+/// by bits of their index.
+const VTK_HEXAHEDRON_ORDER: [usize; 8] = [0, 1, 3, 2, 4, 5, 7, 6];
+
+/// Identifier of hexahedral cells in the VTK format.
+const VTK_HEXAHEDRON: usize = 12;
+
+/// Writing blocks as a legacy VTK unstructured grid, each block being exported as an hexahedral
+/// cell. The velocity magnitude of each block is exported as point data.
+///
+/// * `w` - writer receiving the VTK file.
+/// * `blocks` - blocks to export.
+///
+/// # Examples
+/// ```
+/// use rody::block::*;
+/// use rody::vtk::*;
+///
+/// let block = BlockBuilder::new()
+///     .set_lengths(1.0, 1.0, 1.0)
+///     .set_initial_velocity(3.0, 4.0, 0.0)
+///     .get();
+///
+/// let mut buffer = Vec::new();
+/// write_blocks(&mut buffer, &[block]).unwrap();
+/// let vtk = String::from_utf8(buffer).unwrap();
+///
+/// assert!(vtk.starts_with("# vtk DataFile Version 3.0\n"));
+/// assert!(vtk.contains("DATASET UNSTRUCTURED_GRID\n"));
+/// assert!(vtk.contains("POINTS 8 double\n"));
+/// assert!(vtk.contains("CELLS 1 9\n"));
+/// assert!(vtk.contains("POINT_DATA 8\n"));
+/// ```
+pub fn write_blocks<W: io::Write>(w: &mut W, blocks: &[Block]) -> io::Result<()>
+{
+    writeln!(w, "# vtk DataFile Version 3.0")?;
+    writeln!(w, "rody blocks")?;
+    writeln!(w, "ASCII")?;
+    writeln!(w, "DATASET UNSTRUCTURED_GRID")?;
+
+    writeln!(w, "POINTS {} double", 8 * blocks.len())?;
+    for block in blocks.iter() {
+        let corners = block.get_corners();
+        for i in VTK_HEXAHEDRON_ORDER.iter() {
+            let c = &corners[*i].coords;
+            writeln!(w, "{} {} {}", c.x, c.y, c.z)?;
+        }
+    }
+
+    writeln!(w, "CELLS {} {}", blocks.len(), 9 * blocks.len())?;
+    for i in 0..blocks.len() {
+        let first = 8 * i;
+        writeln!(w, "8 {} {} {} {} {} {} {} {}",
+            first, first + 1, first + 2, first + 3, first + 4, first + 5, first + 6, first + 7)?;
+    }
+
+    writeln!(w, "CELL_TYPES {}", blocks.len())?;
+    for _ in blocks.iter() { writeln!(w, "{}", VTK_HEXAHEDRON)?; }
+
+    writeln!(w, "POINT_DATA {}", 8 * blocks.len())?;
+    writeln!(w, "SCALARS velocity_magnitude double 1")?;
+    writeln!(w, "LOOKUP_TABLE default")?;
+    for block in blocks.iter() {
+        let speed = block.velocity.coords.norm();
+        for _ in 0..8 { writeln!(w, "{}", speed)?; }
+    }
+    Ok(())
+}
+
+/// Writing a single block as a legacy VTK unstructured grid, see `write_blocks`.
+///
+/// * `w` - writer receiving the VTK file.
+/// * `block` - block to export.
+///
+pub fn write_block<W: io::Write>(w: &mut W, block: &Block) -> io::Result<()>
+{
+    write_blocks(w, ::std::slice::from_ref(block))
+}
+
+/// Writing all blocks of a world as a legacy VTK unstructured grid, see `write_blocks`.
+///
+/// * `w` - writer receiving the VTK file.
+/// * `world` - world whose blocks are exported.
+///
+pub fn write_world<W: io::Write>(w: &mut W, world: &World) -> io::Result<()>
+{
+    write_blocks(w, &world.blocks)
+}
@@ -0,0 +1,504 @@
+// Using base tools of mersh.
+use mersh::base::*;
+
+// Using axis-aligned bounding boxes.
+use aabb::*;
+
+// Using blocks.
+use block::*;
+
+// Using time integrators.
+use integrator::*;
+
+// Using collision responses.
+use collision::*;
+
+// Using quaternions.
+use quaternion::*;
+
+use std::collections::HashMap;
+
+/// Boundary conditions applied on blocks on the walls of the domain of a world.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BoundaryCondition {
+    /// Blocks bounce on the walls, their normal velocity being reflected.
+    Reflect,
+    /// Blocks exiting the domain through a wall re-enter it through the opposite wall.
+    Periodic,
+    /// Domain walls are ignored.
+    None,
+}
+
+/// Data structure for defining worlds, i.e. collections of blocks simulated together.
+#[derive(Clone, Default, Debug)]
+pub struct World {
+    /// Blocks of the world.
+    pub blocks: Vec<Block>,
+    /// Optional domain of the world.
+    pub domain: Option<Aabb>,
+    /// Boundary conditions applied on the walls of the domain.
+    pub boundary: BoundaryCondition,
+    /// Optional cell size of the spatial hash grid used for finding collisions, all pairs of
+    /// blocks being tested if not set.
+    pub cell_size: Option<f64>,
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of boundary conditions.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl Default for BoundaryCondition {
+    /// Default boundary condition is the reflection on domain walls.
+    ///
+    fn default() -> Self
+    {
+        BoundaryCondition::Reflect
+    }
+}
+
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+// Implementation of world services.
+//////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////
+
+impl World {
+    /// Creating new empty world.
+    ///
+    pub fn new() -> Self
+    {
+        World::default()
+    }
+
+    /// Setting axis-aligned domain of the world. When stepping the world, boundary conditions are
+    /// applied on blocks crossing a wall of the domain, see `set_boundary_condition`. By default,
+    /// blocks are clamped back inside and their velocity component normal to the wall is reflected,
+    /// scaled by their restitution coefficient.
+    ///
+    /// * `min` - corner of the domain with minimal coordinates.
+    /// * `max` - corner of the domain with maximal coordinates.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use rody::world::*;
+    /// use mersh::base::*;
+    ///
+    /// let mut world = World::new();
+    /// world.set_domain(Pnt3d::new(-2.0, -2.0, -2.0), Pnt3d::new(2.0, 2.0, 2.0))
+    ///     .push(BlockBuilder::new()
+    ///         .set_lengths(1., 1., 1.)
+    ///         .set_initial_position(1.4, 0.0, 0.0)
+    ///         .set_initial_velocity(2.0, 0.0, 0.0)
+    ///         .set_restitution(0.5)
+    ///         .get());
+    ///
+    /// world.step(0.1, &ExplicitEuler::new());
+    ///
+    /// assert!((world.blocks[0].position.coords.x - 1.5).abs() < 1e-12);
+    /// assert!((world.blocks[0].velocity.coords.x + 1.0).abs() < 1e-12);
+    /// ```
+    pub fn set_domain(&mut self, min: Pnt3d, max: Pnt3d) -> &mut Self
+    {
+        self.domain = Some(Aabb::new(min, max));
+        self
+    }
+
+    /// Setting boundary conditions applied on the walls of the domain of the world. For periodic
+    /// boundary conditions, blocks whose center exits the domain through a wall re-enter it through
+    /// the opposite wall, keeping their velocity.
+    ///
+    /// * `boundary` - boundary conditions applied on domain walls.
+    ///
+    /// # Examples
+    /// ```
+    /// extern crate mersh;
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use rody::world::*;
+    /// use mersh::base::*;
+    ///
+    /// let mut world = World::new();
+    /// world.set_domain(Pnt3d::new(-2.0, -2.0, -2.0), Pnt3d::new(2.0, 2.0, 2.0))
+    ///     .set_boundary_condition(BoundaryCondition::Periodic)
+    ///     .push(BlockBuilder::new()
+    ///         .set_lengths(1., 1., 1.)
+    ///         .set_initial_position(1.9, 0.0, 0.0)
+    ///         .set_initial_velocity(2.0, 0.0, 0.0)
+    ///         .get());
+    ///
+    /// world.step(0.1, &ExplicitEuler::new());
+    ///
+    /// assert!((world.blocks[0].position.coords.x + 1.9).abs() < 1e-12);
+    /// assert!((world.blocks[0].velocity.coords.x - 2.0).abs() < 1e-12);
+    /// ```
+    pub fn set_boundary_condition(&mut self, boundary: BoundaryCondition) -> &mut Self
+    {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Setting cell size of the uniform spatial hash grid used for finding collisions. Blocks are
+    /// inserted in every cell overlapped by their axis-aligned bounding box, and only blocks sharing
+    /// a cell are tested against each other. A cell size that is not strictly positive disables the
+    /// grid, all pairs of blocks being tested.
+    ///
+    /// * `cell_size` - length of the edges of the cells of the grid.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::world::*;
+    ///
+    /// // Pseudo-random scene, using a linear congruential generator.
+    /// let mut seed: u64 = 42;
+    /// let mut random = move || {
+    ///     seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    ///     (seed >> 11) as f64 / (1u64 << 53) as f64
+    /// };
+    ///
+    /// let mut world = World::new();
+    /// for _ in 0..200 {
+    ///     let l = 0.1 + 2.0 * random();
+    ///     let (x, y, z) = (20.0 * random() - 10.0, 20.0 * random() - 10.0, 20.0 * random() - 10.0);
+    ///     world.push(BlockBuilder::new().set_lengths(l, 0.5 * l, l).set_initial_position(x, y, z).get());
+    /// }
+    ///
+    /// let brute_force = world.find_collisions();
+    /// world.set_cell_size(1.5);
+    ///
+    /// assert!(!brute_force.is_empty());
+    /// assert_eq!(world.find_collisions(), brute_force);
+    /// ```
+    pub fn set_cell_size(&mut self, cell_size: f64) -> &mut Self
+    {
+        self.cell_size = if cell_size > 0.0 { Some(cell_size) } else { None };
+        self
+    }
+
+    /// Adding block to the world.
+    ///
+    /// * `block` - block to add.
+    ///
+    pub fn push(&mut self, block: Block) -> &mut Self
+    {
+        self.blocks.push(block);
+        self
+    }
+
+    /// Accessing number of blocks of the world.
+    ///
+    pub fn len(&self) -> usize
+    {
+        self.blocks.len()
+    }
+
+    /// Testing if the world has no block.
+    ///
+    pub fn is_empty(&self) -> bool
+    {
+        self.blocks.is_empty()
+    }
+
+    /// Iterating over blocks of the world.
+    ///
+    pub fn iter(&self) -> ::std::slice::Iter<'_, Block>
+    {
+        self.blocks.iter()
+    }
+
+    /// Iterating mutably over blocks of the world.
+    ///
+    pub fn iter_mut(&mut self) -> ::std::slice::IterMut<'_, Block>
+    {
+        self.blocks.iter_mut()
+    }
+
+    /// Computing total translational kinetic energy of the blocks of the world.
+    ///
+    pub fn total_kinetic_energy(&self) -> f64
+    {
+        self.blocks.iter().map(|block| block.get_kinetic_energy()).sum()
+    }
+
+    /// Computing total linear momentum of the blocks of the world.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::collision::*;
+    /// use rody::world::*;
+    ///
+    /// let mut world = World::new();
+    /// world.push(BlockBuilder::new().set_mass(1.0).set_lengths(1., 1., 1.).set_initial_velocity(1.0, 0.0, 0.0).get())
+    ///     .push(BlockBuilder::new().set_mass(3.0).set_lengths(1., 1., 1.).set_initial_position(0.9, 0.2, 0.0).get());
+    ///
+    /// let (p0, e0) = (world.total_linear_momentum(), world.total_kinetic_energy());
+    ///
+    /// let (a, b) = world.blocks.split_at_mut(1);
+    /// resolve_elastic_collision(&mut a[0], &mut b[0]);
+    ///
+    /// let (p1, e1) = (world.total_linear_momentum(), world.total_kinetic_energy());
+    ///
+    /// assert!((world.blocks[1].velocity.coords.x - 0.5).abs() < 1e-12);
+    /// assert!((p0.coords.x - p1.coords.x).abs() < 1e-12);
+    /// assert!((p0.coords.y - p1.coords.y).abs() < 1e-12);
+    /// assert!((e0 - e1).abs() < 1e-12);
+    /// ```
+    pub fn total_linear_momentum(&self) -> Vec3d
+    {
+        let mut momentum = Vec3d::default();
+        for block in self.blocks.iter() { momentum.coords.add_in(1.0, &block.get_linear_momentum().coords); }
+        momentum
+    }
+
+    /// Finding pairs of overlapping blocks. Bounding boxes of blocks accounting for their orientation
+    /// are compared first, see `Block::get_bounding_aabb`, pairs involving a rotated block being
+    /// then tested using `Block::overlaps_oriented`. Pairs are given as indexes of blocks, the first
+    /// index being lower than the second one, sorted in increasing order. If no
+    /// cell size is set, all pairs of blocks are tested, hence the complexity is quadratic in the
+    /// number of blocks. Otherwise, only blocks sharing a cell of the spatial hash grid are tested,
+    /// see `set_cell_size`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::world::*;
+    ///
+    /// let mut world = World::new();
+    /// world.push(BlockBuilder::new().set_lengths(1., 1., 1.).get())
+    ///     .push(BlockBuilder::new().set_lengths(1., 1., 1.).set_initial_position(5.0, 0.0, 0.0).get())
+    ///     .push(BlockBuilder::new().set_lengths(1., 1., 1.).set_initial_position(0.5, 0.5, 0.0).get());
+    ///
+    /// assert_eq!(world.find_collisions(), vec![(0, 2)]);
+    ///
+    /// // Rotated blocks overlapping outside of their unrotated boxes.
+    /// let diamond = |x: f64| BlockBuilder::new()
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_orientation_euler(0.0, 0.0, 0.25 * std::f64::consts::PI)
+    ///     .set_initial_position(x, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let mut world = World::new();
+    /// world.push(diamond(0.0)).push(diamond(1.2));
+    ///
+    /// assert!(!world.blocks[0].overlaps(&world.blocks[1]));
+    /// assert_eq!(world.find_collisions(), vec![(0, 1)]);
+    ///
+    /// world.set_cell_size(1.0);
+    /// assert_eq!(world.find_collisions(), vec![(0, 1)]);
+    /// ```
+    pub fn find_collisions(&self) -> Vec<(usize, usize)>
+    {
+        if let Some(cell_size) = self.cell_size { return self.find_collisions_in_grid(cell_size); }
+
+        let mut collisions = Vec::new();
+        for (i, a) in self.blocks.iter().enumerate()
+        {
+            for (j, b) in self.blocks.iter().enumerate().skip(i + 1)
+            {
+                if World::collide(a, b) { collisions.push((i, j)); }
+            }
+        }
+        collisions
+    }
+
+    /// Finding pairs of overlapping blocks sharing a cell of a uniform spatial hash grid.
+    ///
+    /// * `cell_size` - length of the edges of the cells of the grid.
+    ///
+    fn find_collisions_in_grid(&self, cell_size: f64) -> Vec<(usize, usize)>
+    {
+        // Inserting blocks in every cell overlapped by their bounding box.
+        let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+        for (i, block) in self.blocks.iter().enumerate()
+        {
+            let aabb = block.get_bounding_aabb();
+            let cell = |x: f64| (x / cell_size).floor() as i64;
+            let (min, max) = (&aabb.min.coords, &aabb.max.coords);
+            for cx in cell(min.x)..=cell(max.x) {
+                for cy in cell(min.y)..=cell(max.y) {
+                    for cz in cell(min.z)..=cell(max.z) {
+                        grid.entry([cx, cy, cz]).or_default().push(i);
+                    }
+                }
+            }
+        }
+
+        // Testing blocks sharing a cell, pairs shared by several cells being found several times.
+        let mut collisions = Vec::new();
+        for indexes in grid.values()
+        {
+            for (k, i) in indexes.iter().enumerate()
+            {
+                for j in indexes.iter().skip(k + 1)
+                {
+                    if World::collide(&self.blocks[*i], &self.blocks[*j]) { collisions.push((*i, *j)); }
+                }
+            }
+        }
+        collisions.sort_unstable();
+        collisions.dedup();
+        collisions
+    }
+
+    /// Testing if two blocks overlap, comparing their bounding boxes first and using the separating
+    /// axis test for rotated blocks, see `Block::overlaps_oriented`.
+    ///
+    fn collide(a: &Block, b: &Block) -> bool
+    {
+        if !a.get_bounding_aabb().intersects(&b.get_bounding_aabb()) { return false; }
+
+        let rotated = |block: &Block| !block.orientation.approx_eq(&Quaternion::identity(), 1e-12);
+        !(rotated(a) || rotated(b)) || a.overlaps_oriented(b)
+    }
+
+    /// Advancing every block of the world over a time step. The step proceeds as follows:
+    ///
+    /// 1. every block is advanced using the input integrator,
+    /// 2. pairs of overlapping blocks are found, see `find_collisions`,
+    /// 3. collisions between overlapping blocks are resolved using their restitution coefficients,
+    ///    see `resolve_collision`,
+    /// 4. domain boundary conditions are applied if a domain is set.
+    ///
+    /// Contact normals are computed from the axis-aligned bounding boxes of the blocks, see
+    /// `contact_info`, hence they are approximate for rotated blocks.
+    ///
+    /// * `dt` - time step.
+    /// * `integrator` - integrator used for advancing blocks.
+    ///
+    /// # Examples
+    /// ```
+    /// use rody::block::*;
+    /// use rody::integrator::*;
+    /// use rody::world::*;
+    ///
+    /// let mut world = World::new();
+    /// world.push(BlockBuilder::new().set_initial_velocity(1.0, 0.0, 0.0).get())
+    ///     .push(BlockBuilder::new().set_initial_velocity(0.0, 2.0, 0.0).get())
+    ///     .push(BlockBuilder::new().set_initial_velocity(0.0, 0.0, -3.0).get());
+    ///
+    /// world.step(0.5, &ExplicitEuler::new());
+    ///
+    /// assert_eq!(world.len(), 3);
+    /// assert!((world.blocks[0].position.coords.x - 0.5).abs() < 1e-12);
+    /// assert!((world.blocks[1].position.coords.y - 1.0).abs() < 1e-12);
+    /// assert!((world.blocks[2].position.coords.z + 1.5).abs() < 1e-12);
+    ///
+    /// // Two blocks on a collision course bounce on each other.
+    /// let mut world = World::new();
+    /// world.push(BlockBuilder::new()
+    ///         .set_mass(1.0)
+    ///         .set_lengths(1., 1., 1.)
+    ///         .set_initial_position(-1.0, 0.0, 0.0)
+    ///         .set_initial_velocity(2.0, 0.0, 0.0)
+    ///         .get())
+    ///     .push(BlockBuilder::new()
+    ///         .set_mass(1.0)
+    ///         .set_lengths(1., 1., 1.)
+    ///         .set_initial_position(1.0, 0.0, 0.0)
+    ///         .set_initial_velocity(-2.0, 0.0, 0.0)
+    ///         .get());
+    ///
+    /// world.step(0.3, &SemiImplicitEuler::new());
+    ///
+    /// assert!((world.blocks[0].velocity.coords.x + 2.0).abs() < 1e-12);
+    /// assert!((world.blocks[1].velocity.coords.x - 2.0).abs() < 1e-12);
+    ///
+    /// // A rotated bar does not collide with a block only overlapping its unrotated box.
+    /// let mut world = World::new();
+    /// world.push(BlockBuilder::new()
+    ///         .set_mass(1.0)
+    ///         .set_lengths(4., 0.2, 0.2)
+    ///         .set_orientation_euler(0.0, 0.0, 0.5 * std::f64::consts::PI)
+    ///         .get())
+    ///     .push(BlockBuilder::new()
+    ///         .set_mass(1.0)
+    ///         .set_lengths(1., 1., 1.)
+    ///         .set_initial_position(2.4, 0.0, 0.0)
+    ///         .set_initial_velocity(-1.0, 0.0, 0.0)
+    ///         .get());
+    ///
+    /// world.step(1e-3, &ExplicitEuler::new());
+    ///
+    /// assert!((world.blocks[1].velocity.coords.x + 1.0).abs() < 1e-12);
+    ///
+    /// // Rotated blocks colliding outside of their unrotated boxes bounce on each other.
+    /// let diamond = |x: f64, vx: f64| BlockBuilder::new()
+    ///     .set_mass(1.0)
+    ///     .set_lengths(1., 1., 1.)
+    ///     .set_orientation_euler(0.0, 0.0, 0.25 * std::f64::consts::PI)
+    ///     .set_initial_position(x, 0.0, 0.0)
+    ///     .set_initial_velocity(vx, 0.0, 0.0)
+    ///     .get();
+    ///
+    /// let mut world = World::new();
+    /// world.push(diamond(0.0, 1.0)).push(diamond(1.2, -1.0));
+    ///
+    /// world.step(1e-3, &ExplicitEuler::new());
+    ///
+    /// assert!((world.blocks[0].velocity.coords.x + 1.0).abs() < 1e-12);
+    /// assert!((world.blocks[1].velocity.coords.x - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn step(&mut self, dt: f64, integrator: &dyn Integrator) -> &mut Self
+    {
+        for block in self.blocks.iter_mut() { integrator.step(block, dt); }
+        for (i, j) in self.find_collisions()
+        {
+            let (head, tail) = self.blocks.split_at_mut(j);
+            resolve_collision(&mut head[i], &mut tail[0]);
+        }
+        self.apply_boundary_conditions();
+        self
+    }
+
+    /// Applying domain boundary conditions on every block of the world.
+    ///
+    fn apply_boundary_conditions(&mut self)
+    {
+        let domain = match self.domain {
+            Some(ref domain) => domain.clone(),
+            None => return,
+        };
+        for block in self.blocks.iter_mut()
+        {
+            if block.is_static { continue; }
+            match self.boundary {
+                BoundaryCondition::Reflect => World::reflect(block, &domain),
+                BoundaryCondition::Periodic => World::wrap(block, &domain),
+                BoundaryCondition::None => (),
+            };
+        }
+    }
+
+    /// Wrapping block center inside a periodic domain.
+    ///
+    fn wrap(block: &mut Block, domain: &Aabb)
+    {
+        let min = [domain.min.coords.x, domain.min.coords.y, domain.min.coords.z];
+        let max = [domain.max.coords.x, domain.max.coords.y, domain.max.coords.z];
+        let mut p = [block.position.coords.x, block.position.coords.y, block.position.coords.z];
+
+        for i in 0..3
+        {
+            let l = max[i] - min[i];
+            if l > 0.0 && (p[i] < min[i] || p[i] >= max[i]) { p[i] = min[i] + (p[i] - min[i]).rem_euclid(l); }
+        }
+        block.position = Pnt3d::new(p[0], p[1], p[2]);
+    }
+
+    /// Reflecting block on the walls of a domain, see `Block::clamp_to_domain`. Velocity components
+    /// pushing the block out of the domain are reflected and scaled by the restitution coefficient
+    /// of the block. Blocks strictly larger than the domain along a direction are centered in the
+    /// domain along this direction, with a zero velocity component.
+    ///
+    fn reflect(block: &mut Block, domain: &Aabb)
+    {
+        let scale = -block.restitution;
+        block.shift_into_box(&domain.min, &domain.max, Some(scale));
+    }
+}